@@ -0,0 +1,38 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use miette::NamedSource;
+use rust_lox::Scanner;
+
+/// Roughly `size` bytes of Lox with multi-byte characters in strings and comments.
+fn generate_source(size: usize) -> String {
+    let snippet = r#"// Grüße, 日本語 and ☃ in a comment
+fun greet(name) {
+    var greeting = "héllo " + name + " ☕";
+    for (var i = 0; i < 10; i = i + 1) {
+        if (i >= 5 and name != nil) print greeting; else print i * 2.5;
+    }
+    return [greeting, {"count": 10}];
+}
+"#;
+    snippet.repeat(size / snippet.len() + 1)
+}
+
+fn scan(c: &mut Criterion) {
+    let source = generate_source(4 * 1024 * 1024);
+    let mut group = c.benchmark_group("scanner");
+    group.throughput(Throughput::Bytes(source.len() as u64));
+    group.sample_size(10);
+    group.bench_function("scan 4 MiB", |b| {
+        b.iter_batched(
+            || source.clone(),
+            |source| {
+                let named_source = NamedSource::new("bench", source.clone());
+                Scanner::scan(source, named_source, false).unwrap()
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, scan);
+criterion_main!(benches);
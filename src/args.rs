@@ -1,11 +1,12 @@
 use clap::Parser;
 use directories::UserDirs;
+use rust_lox::Lint;
 use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[command(version, about)]
 pub struct Args {
-    ///
+    /// Lox file to interpret, starts a REPL if omitted
     #[arg()]
     pub file: Option<String>,
 
@@ -19,6 +20,23 @@ pub struct Args {
     /// Convert ast to graphviz instead of interpreting the code (only works for file input)
     #[clap(short, long, default_value_t)]
     pub graphviz: bool,
+
+    /// Reject programs that use variables not declared anywhere before running them
+    #[clap(long, default_value_t)]
+    pub strict: bool,
+
+    /// Silence a warning: unused-variable, unused-parameter, shadowing, unreachable-code,
+    /// undeclared-assignment or self-comparison (repeatable)
+    #[clap(short = 'A', long, value_name = "LINT")]
+    pub allow: Vec<Lint>,
+
+    /// Format the file in place instead of interpreting it
+    #[clap(long, default_value_t)]
+    pub fmt: bool,
+
+    /// Together with --fmt, fail if the file is not formatted instead of rewriting it
+    #[clap(long, requires = "fmt", default_value_t)]
+    pub check: bool,
 }
 
 fn get_default_history_file() -> PathBuf {
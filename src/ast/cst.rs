@@ -0,0 +1,116 @@
+use std::fmt::Display;
+
+use strum::Display;
+
+use super::token::Token;
+
+/// Node of the lossless concrete syntax tree. Unlike the AST it keeps every token, including
+/// punctuation and trivia, and the surface syntax such as `for` loops.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(Token),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum SyntaxKind {
+    Program,
+    ClassDeclaration,
+    Superclass,
+    Method,
+    FunctionDeclaration,
+    Parameters,
+    TypeAnnotation,
+    VarDeclaration,
+    Initializer,
+    ExpressionStatement,
+    PrintStatement,
+    ReturnStatement,
+    BreakStatement,
+    ContinueStatement,
+    IfStatement,
+    ElseBranch,
+    WhileStatement,
+    ForStatement,
+    Block,
+    Assignment,
+    Logical,
+    Binary,
+    Unary,
+    Call,
+    Arguments,
+    Get,
+    Index,
+    Grouping,
+    Literal,
+    /// Interpolated string, the expressions are children between the fragments of the string.
+    Interpolation,
+    Variable,
+    This,
+    Super,
+    List,
+    Map,
+    MapEntry,
+}
+
+impl SyntaxNode {
+    pub fn new(kind: SyntaxKind, children: Vec<SyntaxElement>) -> Self {
+        Self { kind, children }
+    }
+
+    /// All tokens of the subtree in source order.
+    pub fn tokens(&self) -> Vec<&Token> {
+        let mut tokens = vec![];
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a Token>) {
+        self.children.iter().for_each(|child| match child {
+            SyntaxElement::Node(node) => node.collect_tokens(tokens),
+            SyntaxElement::Token(token) => tokens.push(token),
+        })
+    }
+
+    pub fn child_nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+}
+
+impl From<SyntaxNode> for SyntaxElement {
+    fn from(node: SyntaxNode) -> Self {
+        SyntaxElement::Node(node)
+    }
+}
+
+impl From<Token> for SyntaxElement {
+    fn from(token: Token) -> Self {
+        SyntaxElement::Token(token)
+    }
+}
+
+/// Writes the exact source the tree was parsed from.
+impl Display for SyntaxNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.tokens().iter().try_for_each(|token| {
+            token
+                .leading_trivia
+                .iter()
+                .try_for_each(|trivia| write!(f, "{}", trivia.text))?;
+            write!(f, "{}", token.text())?;
+            token
+                .trailing_trivia
+                .iter()
+                .try_for_each(|trivia| write!(f, "{}", trivia.text))
+        })
+    }
+}
@@ -114,8 +114,31 @@ impl Display for Expr {
             }
             Get(object, name) => write!(f, "(Get {}.{})", object, name.name),
             Set(object, name, value) => write!(f, "(Set {}.{} = {})", object, name.name, value),
-            This => write!(f, "this"),
-            Super(method) => write!(f, "(super.{})", method.name),
+            This(_) => write!(f, "this"),
+            Super(_, method) => write!(f, "(super.{})", method.name),
+            List(elements) => {
+                write!(f, "(List ")?;
+                elements
+                    .iter()
+                    .try_for_each(|element| write!(f, "{}, ", element))?;
+                write!(f, ")")
+            }
+            Map(entries) => {
+                write!(f, "(Map ")?;
+                entries
+                    .iter()
+                    .try_for_each(|(key, value)| write!(f, "{}: {}, ", key, value))?;
+                write!(f, ")")
+            }
+            Index(object, index) => write!(f, "(Index {}[{}])", object, index),
+            SetIndex(object, index, value) => {
+                write!(f, "(SetIndex {}[{}] = {})", object, index, value)
+            }
+            Interpolation(parts) => {
+                write!(f, "(Interpolation ")?;
+                parts.iter().try_for_each(|part| write!(f, "{}, ", part))?;
+                write!(f, ")")
+            }
         }
     }
 }
@@ -132,8 +155,15 @@ pub enum ExprType {
     Call(Box<Expr>, Vec<Expr>),
     Get(Box<Expr>, NameExpr),
     Set(Box<Expr>, NameExpr, Box<Expr>),
-    This,
-    Super(NameExpr),
+    This(NameExpr),
+    /// The `super` keyword and the method looked up in the superclass.
+    Super(NameExpr, NameExpr),
+    List(Vec<Expr>),
+    Map(Vec<(Expr, Expr)>),
+    Index(Box<Expr>, Box<Expr>),
+    SetIndex(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// Parts of an interpolated string, literal parts are string literals.
+    Interpolation(Vec<Expr>),
 }
 
 impl ExprType {
@@ -158,11 +188,7 @@ impl ExprType {
     }
 
     pub fn variable(name: String, src: Arc<NamedSource<String>>, location: SourceSpan) -> ExprType {
-        Self::Variable(NameExpr {
-            name: Name::new(name),
-            location,
-            src: src.clone(),
-        })
+        Self::Variable(NameExpr::new(Name::new(name), location, src))
     }
 
     pub fn assign(name: NameExpr, expr: Expr) -> ExprType {
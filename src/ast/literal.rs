@@ -7,6 +7,17 @@ pub enum Literal {
     Boolean(bool),
     Nil,
 }
+impl Literal {
+    /// Like [`crate::Value::is_truthy`] for the value of the literal.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Literal::Boolean(bool) => *bool,
+            Literal::Nil => false,
+            _ => true,
+        }
+    }
+}
+
 impl Display for Literal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
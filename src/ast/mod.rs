@@ -1,3 +1,4 @@
+pub mod cst;
 pub mod expr;
 pub mod literal;
 pub mod name;
@@ -9,14 +9,47 @@ pub struct NameExpr {
     pub name: Name,
     pub location: SourceSpan,
     pub src: Arc<NamedSource<String>>,
+    /// Where the variable lives if it is local, assigned by the [`crate::Resolver`]. `None`
+    /// for globals, which are looked up by name.
+    pub slot: Option<Slot>,
+}
+
+/// A local variable `depth` environments up from the one it is used in, at `index` in the
+/// values of that environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Slot {
+    pub depth: usize,
+    pub index: usize,
+}
+
+impl Slot {
+    pub fn new(depth: usize, index: usize) -> Self {
+        Slot { depth, index }
+    }
+}
+
+impl Display for Slot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.depth, self.index)
+    }
 }
 
 impl NameExpr {
+    pub fn new(name: Name, location: SourceSpan, src: Arc<NamedSource<String>>) -> Self {
+        NameExpr {
+            name,
+            location,
+            src,
+            slot: None,
+        }
+    }
+
     pub fn this(location: SourceSpan, src: Arc<NamedSource<String>>) -> Self {
         NameExpr {
             name: Name::this(),
             location,
             src: src.clone(),
+            slot: None,
         }
     }
 
@@ -25,10 +58,17 @@ impl NameExpr {
             name: Name::super_name(),
             location,
             src: src.clone(),
+            slot: None,
         }
     }
 }
 
+impl Display for NameExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Name(String);
 const INIT: &str = "init";
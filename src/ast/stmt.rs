@@ -2,10 +2,8 @@ use std::{fmt::Display, sync::Arc};
 
 use miette::{NamedSource, SourceSpan};
 
-use super::{
-    expr::Expr,
-    name::{Name, NameExpr},
-};
+use super::{expr::Expr, name::NameExpr};
+use crate::interpreter::types::Type;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Stmt {
@@ -33,14 +31,16 @@ impl Stmt {
     }
 
     pub fn var(
-        name: String,
+        name: NameExpr,
+        annotation: Option<TypeAnnotation>,
         expr: Option<Expr>,
         location: SourceSpan,
         src: Arc<NamedSource<String>>,
     ) -> Self {
         Stmt {
             stmt_type: StmtType::Var {
-                name: name.into(),
+                name,
+                annotation,
                 initializer: expr,
             },
             src,
@@ -49,7 +49,7 @@ impl Stmt {
     }
 
     pub fn class(
-        name: String,
+        name: NameExpr,
         methods: Vec<Function>,
         superclass: Option<NameExpr>,
         location: SourceSpan,
@@ -57,7 +57,7 @@ impl Stmt {
     ) -> Self {
         Stmt {
             stmt_type: StmtType::Class {
-                name: name.into(),
+                name,
                 methods,
                 superclass,
             },
@@ -84,12 +84,18 @@ impl Stmt {
         }
     }
 
-    pub fn while_stmt(condition: Expr, body: Stmt, location: SourceSpan) -> Self {
+    pub fn while_stmt(
+        condition: Expr,
+        body: Stmt,
+        increment: Option<Expr>,
+        location: SourceSpan,
+    ) -> Self {
         let src = condition.src.clone();
         Stmt {
             stmt_type: StmtType::While {
                 condition,
                 body: body.into(),
+                increment,
             },
             src,
             location,
@@ -102,7 +108,8 @@ pub enum StmtType {
     Expression(Expr),
     Print(Expr),
     Var {
-        name: Name,
+        name: NameExpr,
+        annotation: Option<TypeAnnotation>,
         initializer: Option<Expr>,
     },
     Function(Function),
@@ -116,9 +123,12 @@ pub enum StmtType {
     While {
         condition: Expr,
         body: Box<Stmt>,
+        increment: Option<Expr>, // increment of a desugared for loop, also executed on continue
     },
+    Break,
+    Continue,
     Class {
-        name: Name,
+        name: NameExpr,
         methods: Vec<Function>,
         superclass: Option<NameExpr>,
     },
@@ -126,18 +136,42 @@ pub enum StmtType {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Function {
-    pub name: Name,
-    pub parameters: Vec<Name>,
+    pub name: NameExpr,
+    pub parameters: Vec<NameExpr>,
+    /// One per parameter, `None` for parameters without annotation.
+    pub parameter_types: Vec<Option<TypeAnnotation>>,
+    pub return_type: Option<TypeAnnotation>,
     pub body: Vec<Stmt>,
 }
 
+/// The `: Type` after a variable, parameter or parameter list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypeAnnotation {
+    pub ty: Type,
+    pub location: SourceSpan,
+}
+
+impl Display for TypeAnnotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, ": {}", self.ty)
+    }
+}
+
 impl Display for Function {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "fun {}(", self.name)?;
         self.parameters
             .iter()
-            .try_for_each(|arg| write!(f, "{arg}, "))?;
-        writeln!(f, ") {{")?;
+            .zip(&self.parameter_types)
+            .try_for_each(|(arg, ty)| match ty {
+                Some(ty) => write!(f, "{arg}{ty}, "),
+                None => write!(f, "{arg}, "),
+            })?;
+        write!(f, ")")?;
+        if let Some(return_type) = &self.return_type {
+            write!(f, "{return_type}")?;
+        }
+        writeln!(f, " {{")?;
         self.body.iter().try_for_each(|s| write!(f, "{}", s))?;
         writeln!(f, "}}")
     }
@@ -151,12 +185,18 @@ impl Display for Stmt {
             Print(expr) => writeln!(f, "Print{expr}"),
             Var {
                 name,
-                initializer: Some(expr),
-            } => writeln!(f, "Var {name} = {expr}"),
-            Var {
-                name,
-                initializer: None,
-            } => writeln!(f, "Var {name}"),
+                annotation,
+                initializer,
+            } => {
+                write!(f, "Var {name}")?;
+                if let Some(annotation) = annotation {
+                    write!(f, "{annotation}")?;
+                }
+                match initializer {
+                    Some(expr) => writeln!(f, " = {expr}"),
+                    None => writeln!(f),
+                }
+            }
             Block(stmts) => {
                 writeln!(f, "{{")?;
                 stmts.iter().try_for_each(|s| write!(f, "{}", s))?;
@@ -182,11 +222,20 @@ impl Display for Stmt {
                 write!(f, "{}", then_branch)?;
                 writeln!(f, "endif")
             }
-            While { condition, body } => {
+            While {
+                condition,
+                body,
+                increment,
+            } => {
                 writeln!(f, "while {} {{", condition)?;
                 write!(f, "{}", body)?;
+                if let Some(increment) = increment {
+                    writeln!(f, "increment {increment}")?;
+                }
                 writeln!(f, "}}")
             }
+            Break => writeln!(f, "break"),
+            Continue => writeln!(f, "continue"),
             Function(function) => write!(f, "{function}"),
             Return(None) => writeln!(f, "return"),
             Return(Some(expr)) => writeln!(f, "return {expr}"),
@@ -8,6 +8,11 @@ pub struct Token {
     pub token_type: TokenType,
     pub location: SourceSpan,
     pub src: Arc<NamedSource<String>>,
+    /// Only filled by [`Scanner::scan_with_trivia`](crate::Scanner::scan_with_trivia): the
+    /// whitespace and comments since the previous token's trailing trivia.
+    pub leading_trivia: Vec<Trivia>,
+    /// Whitespace and comments after the token up to the end of its line.
+    pub trailing_trivia: Vec<Trivia>,
 }
 
 impl Token {
@@ -16,8 +21,31 @@ impl Token {
             token_type,
             location,
             src,
+            leading_trivia: vec![],
+            trailing_trivia: vec![],
         }
     }
+
+    /// The token as written in the source, without trivia.
+    pub fn text(&self) -> &str {
+        let start = self.location.offset();
+        &self.src.inner()[start..start + self.location.len()]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+    pub location: SourceSpan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    Whitespace,
+    Newline,
+    LineComment,
+    BlockComment,
 }
 
 #[derive(Debug, Clone, PartialEq, Display)]
@@ -32,6 +60,10 @@ pub enum TokenType {
     LeftBrace,
     #[strum(serialize = "}")]
     RightBrace,
+    #[strum(serialize = "[")]
+    LeftBracket,
+    #[strum(serialize = "]")]
+    RightBracket,
     #[strum(serialize = ",")]
     Comma,
     #[strum(serialize = ".")]
@@ -42,6 +74,8 @@ pub enum TokenType {
     Plus,
     #[strum(serialize = ";")]
     Semicolon,
+    #[strum(serialize = ":")]
+    Colon,
     #[strum(serialize = "/")]
     Slash,
     #[strum(serialize = "*")]
@@ -68,11 +102,18 @@ pub enum TokenType {
     // Literals
     Identifier(String),
     String(String),
+    /// String literal containing `${...}`
+    Interpolation(Vec<StringPart>),
+    /// Text of an interpolated string around its `${...}` expressions, only found in the
+    /// concrete syntax tree.
+    StringFragment,
     Number(f64),
 
     // Keywords.
     And,
+    Break,
     Class,
+    Continue,
     Else,
     False,
     Fun,
@@ -91,3 +132,11 @@ pub enum TokenType {
     // Eof
     Eof,
 }
+
+/// Piece of an interpolated string literal with its location inside the literal.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringPart {
+    Literal(String, SourceSpan),
+    /// Tokens between `${` and `}`, the location includes both.
+    Expression(Vec<Token>, SourceSpan),
+}
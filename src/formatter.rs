@@ -0,0 +1,301 @@
+use miette::NamedSource;
+
+use crate::{
+    ast::{
+        cst::{SyntaxElement, SyntaxKind, SyntaxNode},
+        token::{Token, TokenType, TriviaKind},
+    },
+    parser::Parser,
+    scanner::Scanner,
+};
+
+const INDENT: &str = "  ";
+
+/// Prints programs in the canonical style: one statement per line, two space indentation,
+/// braces on the line of their statement and single spaces around binary operators.
+/// Comments are kept, blank lines between statements are collapsed to one.
+#[derive(Default)]
+pub struct Formatter {
+    out: String,
+    indent: usize,
+    /// Nothing but indentation has been written on the current line.
+    line_start: bool,
+    /// The current line continues a statement broken by a comment.
+    continuation: bool,
+    pending_space: bool,
+    /// A line comment was written, the next token has to start a new line.
+    pending_break: bool,
+    /// A blank line in the source before the next token is kept.
+    blank_line_allowed: bool,
+}
+
+impl Formatter {
+    pub fn format(source: String, named_source: NamedSource<String>) -> miette::Result<String> {
+        let tokens = Scanner::scan_with_trivia(source, named_source)?;
+        let tree = Parser::parse_lossless(tokens)?;
+        Ok(Self::format_tree(&tree))
+    }
+
+    pub fn format_tree(tree: &SyntaxNode) -> String {
+        let mut formatter = Self {
+            line_start: true,
+            ..Self::default()
+        };
+        formatter.node(tree);
+        formatter.out
+    }
+
+    fn node(&mut self, node: &SyntaxNode) {
+        match node.kind {
+            SyntaxKind::Program => self.program(node),
+            SyntaxKind::Block | SyntaxKind::ClassDeclaration => self.with_body(node),
+            kind => {
+                let mut previous = None;
+                for element in &node.children {
+                    if previous.is_some() && space_between(kind, previous, element) {
+                        self.space();
+                    }
+                    self.element(element);
+                    previous = Some(element);
+                }
+            }
+        }
+    }
+
+    fn element(&mut self, element: &SyntaxElement) {
+        match element {
+            SyntaxElement::Node(node) => self.node(node),
+            SyntaxElement::Token(token) => self.token(token),
+        }
+    }
+
+    fn program(&mut self, program: &SyntaxNode) {
+        let mut first = true;
+        for statement in program.child_nodes() {
+            self.blank_line_allowed = !first;
+            self.node(statement);
+            self.end_line();
+            first = false;
+        }
+        // comments at the end of the file are attached to Eof
+        if let Some(SyntaxElement::Token(eof)) = program.children.last() {
+            self.blank_line_allowed = !first;
+            self.leading_comments(eof);
+        }
+        self.end_line();
+    }
+
+    /// Blocks and class declarations, the nodes before the opening brace stay on its line and
+    /// the members are indented.
+    fn with_body(&mut self, node: &SyntaxNode) {
+        let mut children = node.children.iter();
+        let mut previous = None;
+        for element in children.by_ref() {
+            if previous.is_some() {
+                self.space();
+            }
+            self.element(element);
+            if matches!(element, SyntaxElement::Token(t) if t.token_type == TokenType::LeftBrace) {
+                break;
+            }
+            previous = Some(element);
+        }
+        let (members, closing): (Vec<_>, Vec<_>) =
+            children.partition(|element| matches!(element, SyntaxElement::Node(_)));
+        let closing = closing.into_iter().find_map(|element| match element {
+            SyntaxElement::Token(token) => Some(token),
+            SyntaxElement::Node(_) => None,
+        });
+        let has_comments = closing.is_some_and(|token| {
+            token
+                .leading_trivia
+                .iter()
+                .any(|trivia| is_comment(trivia.kind))
+        });
+        if members.is_empty() && !has_comments && !self.pending_break {
+            closing.into_iter().for_each(|token| self.token(token));
+            return;
+        }
+        self.end_line();
+        self.indent += 1;
+        for (index, member) in members.iter().enumerate() {
+            self.blank_line_allowed = index > 0;
+            self.element(member);
+            self.end_line();
+        }
+        if let Some(token) = closing {
+            self.blank_line_allowed = !members.is_empty();
+            self.leading_comments(token);
+            self.indent -= 1;
+            self.blank_line_allowed = false;
+            self.text(token);
+        } else {
+            self.indent -= 1;
+        }
+    }
+
+    fn token(&mut self, token: &Token) {
+        if self.leading_comments(token) && self.blank_line_allowed {
+            self.blank_line();
+        }
+        self.blank_line_allowed = false;
+        self.text(token);
+    }
+
+    /// Writes the comments before a token on lines of their own, returns whether a blank
+    /// line separates them from the token.
+    fn leading_comments(&mut self, token: &Token) -> bool {
+        let mut newlines = 0;
+        for trivia in &token.leading_trivia {
+            match trivia.kind {
+                TriviaKind::Newline => newlines += 1,
+                TriviaKind::Whitespace => (),
+                TriviaKind::LineComment | TriviaKind::BlockComment => {
+                    if !self.line_start {
+                        self.new_line(true);
+                    } else if newlines > 1 && self.blank_line_allowed {
+                        self.blank_line();
+                    }
+                    self.pending_space = false;
+                    self.write(&trivia.text);
+                    self.new_line(self.continuation);
+                    self.blank_line_allowed = !self.continuation;
+                    newlines = 0;
+                }
+            }
+        }
+        newlines > 1
+    }
+
+    /// The token and the comments following it on its line.
+    fn text(&mut self, token: &Token) {
+        self.write(token.text());
+        for trivia in &token.trailing_trivia {
+            match trivia.kind {
+                TriviaKind::LineComment => {
+                    self.space();
+                    self.write(&trivia.text);
+                    self.pending_break = true;
+                }
+                TriviaKind::BlockComment => {
+                    self.space();
+                    self.write(&trivia.text);
+                    self.space();
+                }
+                TriviaKind::Whitespace | TriviaKind::Newline => (),
+            }
+        }
+    }
+
+    fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if self.pending_break {
+            self.new_line(true);
+        }
+        if self.line_start {
+            let depth = self.indent + usize::from(self.continuation);
+            self.out.push_str(&INDENT.repeat(depth));
+            self.line_start = false;
+        } else if self.pending_space {
+            self.out.push(' ');
+        }
+        self.pending_space = false;
+        self.out.push_str(text);
+    }
+
+    fn space(&mut self) {
+        self.pending_space = true;
+    }
+
+    fn new_line(&mut self, continuation: bool) {
+        if !self.line_start {
+            self.out.push('\n');
+        }
+        self.line_start = true;
+        self.continuation = continuation;
+        self.pending_space = false;
+        self.pending_break = false;
+    }
+
+    fn end_line(&mut self) {
+        self.new_line(false)
+    }
+
+    fn blank_line(&mut self) {
+        if self.line_start && !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+}
+
+fn is_comment(kind: TriviaKind) -> bool {
+    matches!(kind, TriviaKind::LineComment | TriviaKind::BlockComment)
+}
+
+/// Whether the children of a node of `kind` are separated by a space.
+fn space_between(
+    kind: SyntaxKind,
+    previous: Option<&SyntaxElement>,
+    element: &SyntaxElement,
+) -> bool {
+    use SyntaxKind::*;
+    let is_token = |element: Option<&SyntaxElement>, expected: TokenType| matches!(element, Some(SyntaxElement::Token(t)) if t.token_type == expected);
+    let is_node = matches!(element, SyntaxElement::Node(_));
+    let is_annotation = matches!(element, SyntaxElement::Node(node) if node.kind == TypeAnnotation);
+    match kind {
+        VarDeclaration => !is_token(Some(element), TokenType::Semicolon) && !is_annotation,
+        Initializer | PrintStatement | ReturnStatement | ElseBranch | MapEntry => is_node,
+        TypeAnnotation => is_token(previous, TokenType::Colon),
+        FunctionDeclaration | Method => {
+            !matches!(element, SyntaxElement::Node(node) if matches!(node.kind, Parameters | TypeAnnotation))
+        }
+        Parameters | Arguments | List | Map => is_token(previous, TokenType::Comma),
+        IfStatement | WhileStatement => {
+            is_token(Some(element), TokenType::LeftParen)
+                || is_token(previous, TokenType::RightParen)
+                || matches!(element, SyntaxElement::Node(node) if node.kind == ElseBranch)
+        }
+        ForStatement => {
+            is_token(Some(element), TokenType::LeftParen)
+                || is_token(previous, TokenType::RightParen)
+                || (is_node
+                    && (is_token(previous, TokenType::Semicolon)
+                        || matches!(previous, Some(SyntaxElement::Node(_)))))
+        }
+        Assignment | Logical | Binary | Superclass => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use miette::NamedSource;
+
+    use super::Formatter;
+
+    fn format(source: &str) -> String {
+        Formatter::format(source.to_string(), NamedSource::new("", source.to_string())).unwrap()
+    }
+
+    #[test]
+    fn normalizes_spacing() {
+        assert_eq!(
+            format("var  a=1+2*-3 ;print a ;"),
+            "var a = 1 + 2 * -3;\nprint a;\n"
+        );
+    }
+
+    #[test]
+    fn comment_inside_expression() {
+        let formatted = format("print f(1, // one\n2);");
+        assert_eq!(formatted, "print f(1, // one\n  2);\n");
+        assert_eq!(format(&formatted), formatted);
+    }
+
+    #[test]
+    fn empty_program() {
+        assert_eq!(format("  \n\n"), "");
+    }
+}
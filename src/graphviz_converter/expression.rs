@@ -40,10 +40,15 @@ impl GraphvizConverter for ExprType {
                 single_child(format!("Get expression \"{}\"", name.name).as_str(), expr)
             }
             ExprType::Set(object, name, value) => set(object, &name.name, value),
-            ExprType::This => GraphvizRepr::single(expr("this")),
-            ExprType::Super(name) => {
+            ExprType::This(_) => GraphvizRepr::single(expr("this")),
+            ExprType::Super(_, name) => {
                 GraphvizRepr::single(expr(format!("super.{}", name.name).as_str()))
             }
+            ExprType::List(elements) => list("list", elements),
+            ExprType::Map(entries) => map(entries),
+            ExprType::Index(object, index) => index_expr("index", object, index),
+            ExprType::SetIndex(object, index, value) => set_index(object, index, value),
+            ExprType::Interpolation(parts) => list("interpolation", parts),
         }
     }
 }
@@ -101,3 +106,46 @@ fn set(object: &Expr, name: &Name, value: &Expr) -> GraphvizRepr {
     node.push(edge!(node.id.clone() => value.id.clone(); attr!("label", "value")));
     node
 }
+
+fn list(label: &str, elements: &[Expr]) -> GraphvizRepr {
+    let mut node = GraphvizRepr::single(expr(label));
+    elements.iter().for_each(|e| {
+        let e = e.to_graphviz();
+        node.append(e.stmts);
+        node.push(edge!(node.id.clone() => e.id))
+    });
+    node
+}
+
+fn map(entries: &[(Expr, Expr)]) -> GraphvizRepr {
+    let mut node = GraphvizRepr::single(expr("map"));
+    entries.iter().for_each(|(key, value)| {
+        let key = key.to_graphviz();
+        node.append(key.stmts);
+        node.push(edge!(node.id.clone() => key.id.clone(); attr!("label", "key")));
+        let value = value.to_graphviz();
+        node.append(value.stmts);
+        node.push(edge!(key.id => value.id; attr!("label", "value")));
+    });
+    node
+}
+
+fn index_expr(label: &str, object: &Expr, index: &Expr) -> GraphvizRepr {
+    let mut node = GraphvizRepr::single(expr(label));
+    let object = object.to_graphviz();
+    node.stmts.extend(object.stmts);
+    node.push(edge!(node.id.clone() => object.id.clone(); attr!("label", "object")));
+
+    let index = index.to_graphviz();
+    node.stmts.extend(index.stmts);
+    node.push(edge!(node.id.clone() => index.id.clone(); attr!("label", "index")));
+    node
+}
+
+fn set_index(object: &Expr, index: &Expr, value: &Expr) -> GraphvizRepr {
+    let mut node = index_expr("set index", object, index);
+    let value = value.to_graphviz();
+    node.stmts.extend(value.stmts);
+    node.push(edge!(node.id.clone() => value.id.clone(); attr!("label", "value")));
+    node
+}
@@ -19,9 +19,9 @@ impl GraphvizConverter for StmtType {
         match self {
             StmtType::Expression(expr) => single_expr("Expr", expr),
             StmtType::Print(expr) => single_expr("print", expr),
-            StmtType::Var { name, initializer } => {
-                single_with_option_expr(format!("var {}", name).as_str(), initializer)
-            }
+            StmtType::Var {
+                name, initializer, ..
+            } => single_with_option_expr(format!("var {}", name).as_str(), initializer),
             StmtType::Function(f) => function(f, "fun"),
             StmtType::Return(expr) => single_with_option_expr("return", expr),
             StmtType::Block(stmts) => block(stmts, "block"),
@@ -30,12 +30,18 @@ impl GraphvizConverter for StmtType {
                 then_stmt,
                 else_stmt,
             } => convert_if(condition, then_stmt, else_stmt.as_deref()),
-            StmtType::While { condition, body } => convert_while(condition, body),
+            StmtType::While {
+                condition,
+                body,
+                increment,
+            } => convert_while(condition, body, increment),
+            StmtType::Break => GraphvizRepr::single(stmt("break")),
+            StmtType::Continue => GraphvizRepr::single(stmt("continue")),
             StmtType::Class {
                 name,
                 methods,
                 superclass,
-            } => class(name, methods, superclass),
+            } => class(&name.name, methods, superclass),
         }
     }
 }
@@ -120,7 +126,7 @@ fn convert_if(
     node
 }
 
-fn convert_while(condition: &Expr, body: &stmt::Stmt) -> GraphvizRepr {
+fn convert_while(condition: &Expr, body: &stmt::Stmt, increment: &Option<Expr>) -> GraphvizRepr {
     let mut node = GraphvizRepr::single(stmt("while"));
     let mut ids = vec![];
     let condition = condition.to_graphviz();
@@ -134,6 +140,11 @@ fn convert_while(condition: &Expr, body: &stmt::Stmt) -> GraphvizRepr {
     subgraph.stmts.push(rank_subgraph.into());
     node.push(edge!(node.id.clone() => condition.id; attr!("label", "condition")));
     node.push(edge!(node.id.clone() => body.id; attr!("label", "body")));
+    increment.iter().for_each(|i| {
+        let increment = i.to_graphviz();
+        node.append(increment.stmts);
+        node.push(edge!(node.id.clone() => increment.id; attr!("label", "increment")));
+    });
     node.stmts.push(subgraph.into());
     node.stmts.append(&mut rank_edges);
     node
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use miette::{Diagnostic, NamedSource, SourceSpan};
+
+/// A call that has not returned yet, innermost frames are at the end of the stack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub function: String,
+    pub src: Arc<NamedSource<String>>,
+    pub location: SourceSpan,
+}
+
+/// One entry of the trace attached to runtime errors, consecutive identical frames of a
+/// recursion are collapsed into one.
+#[derive(thiserror::Error, Debug, Diagnostic, Clone)]
+#[error("in {function}{}", if *.times > 1 { format!(" ({} times)", .times) } else { String::new() })]
+#[diagnostic(severity(Advice))]
+pub struct CalledFrom {
+    pub function: String,
+    pub times: usize,
+    #[source_code]
+    pub src: Arc<NamedSource<String>>,
+    #[label("called here")]
+    pub location: SourceSpan,
+}
+
+pub(super) fn trace(call_stack: &[Frame]) -> Vec<CalledFrom> {
+    let mut trace: Vec<CalledFrom> = vec![];
+    for frame in call_stack.iter().rev() {
+        match trace.last_mut() {
+            Some(last)
+                if last.function == frame.function
+                    && last.location == frame.location
+                    && Arc::ptr_eq(&last.src, &frame.src) =>
+            {
+                last.times += 1
+            }
+            _ => trace.push(CalledFrom {
+                function: frame.function.clone(),
+                times: 1,
+                src: frame.src.clone(),
+                location: frame.location,
+            }),
+        }
+    }
+    trace
+}
@@ -2,7 +2,11 @@ use core::fmt::Display;
 
 use self::Callable::*;
 use super::{
-    class::Class, function::Function, native_functions::Native, value::Value, Interpreter, Result,
+    class::Class,
+    function::Function,
+    native_functions::{CallSite, Native},
+    value::Value,
+    Interpreter, Result,
 };
 #[derive(Debug, Clone, PartialEq)]
 pub enum Callable {
@@ -12,14 +16,27 @@ pub enum Callable {
 }
 
 impl Callable {
-    pub fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value> {
+    pub fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+        call_site: &CallSite,
+    ) -> Result<Value> {
         match self {
-            Native(native) => native.call(interpreter, arguments),
+            Native(native) => native.call(interpreter, arguments, call_site),
             Function(function) => function.call(interpreter, arguments),
             Class(class) => class.call(interpreter, arguments),
         }
     }
 
+    pub fn name(&self) -> String {
+        match self {
+            Native(native) => native.name().to_string(),
+            Function(function) => function.name().to_string(),
+            Class(class) => class.name().to_string(),
+        }
+    }
+
     pub fn arity(&self) -> usize {
         match self {
             Native(native) => native.arity(),
@@ -67,6 +67,10 @@ impl Class {
         Ok(Value::Instance(instance))
     }
 
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
     pub fn arity(&self) -> usize {
         self.find_method(&Name::init())
             .map(|m| m.arity())
@@ -1,72 +1,73 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{cell::RefCell, rc::Rc};
 
-use crate::ast::name::Name;
+use crate::ast::name::Slot;
 
-use super::{callable::Callable, native_functions::native_functions, value::Value};
+use super::value::Value;
 
-#[derive(Debug, PartialEq)]
+/// The values of a local scope, at the indices the resolver assigned to their declarations.
+/// Globals are not part of any environment, they are looked up by name.
+#[derive(Debug, PartialEq, Default)]
 pub struct Environment {
     pub parent: Option<Rc<RefCell<Environment>>>,
-    values: HashMap<Name, Value>,
+    values: Vec<Value>,
 }
 
 impl Environment {
     pub fn new() -> Self {
-        Environment {
-            parent: None,
-            values: HashMap::new(),
-        }
+        Self::default()
     }
 
     pub fn from_parent(parent: Rc<RefCell<Environment>>) -> Self {
         Environment {
             parent: Some(parent),
-            values: HashMap::new(),
+            values: vec![],
         }
     }
 
-    pub fn with_native_functions() -> Self {
-        let mut env = Self::new();
-        native_functions()
-            .into_iter()
-            .for_each(|(k, v)| env.define(&k, Value::Callable(Callable::Native(v))));
-        env
-    }
-
-    pub fn define(&mut self, key: &Name, value: Value) {
-        self.values.insert(key.clone(), value);
-    }
-
-    pub fn get(&self, key: &Name) -> Option<Value> {
-        self.values.get(key).cloned()
+    pub fn define(&mut self, index: usize, value: Value) {
+        if index >= self.values.len() {
+            self.values.resize(index + 1, Value::Nil);
+        }
+        self.values[index] = value;
     }
 
-    pub fn assign(&mut self, key: &Name, value: &Value) -> bool {
-        self.values
-            .get_mut(key)
-            .map(|old| *old = value.clone())
-            .is_some()
+    pub fn get_at(&self, slot: Slot) -> Option<Value> {
+        if slot.depth == 0 {
+            self.values.get(slot.index).cloned()
+        } else {
+            self.ancestor(slot.depth)
+                .borrow()
+                .values
+                .get(slot.index)
+                .cloned()
+        }
     }
 
-    pub fn get_at(&self, distance: usize, name: &Name) -> Option<Value> {
-        if distance == 0 {
-            self.get(name)
+    pub fn assign_at(&mut self, slot: Slot, value: &Value) -> bool {
+        let assign = |values: &mut Vec<Value>| {
+            values
+                .get_mut(slot.index)
+                .map(|old| *old = value.clone())
+                .is_some()
+        };
+        if slot.depth == 0 {
+            assign(&mut self.values)
         } else {
-            self.parent
-                .as_ref()
-                .and_then(|p| p.borrow().get_at(distance - 1, name))
+            assign(&mut self.ancestor(slot.depth).borrow_mut().values)
         }
     }
 
-    pub fn assign_at(&mut self, distance: usize, name: &Name, value: &Value) -> bool {
-        if distance == 0 {
-            self.assign(name, value)
-        } else {
-            self.parent
-                .as_ref()
-                .map(|p| p.borrow_mut().assign_at(distance - 1, name, value))
-                .expect("guaranteed by resolver")
+    fn ancestor(&self, depth: usize) -> Rc<RefCell<Environment>> {
+        let mut environment = self.parent.clone().expect("guaranteed by resolver");
+        for _ in 1..depth {
+            let parent = environment
+                .borrow()
+                .parent
+                .clone()
+                .expect("guaranteed by resolver");
+            environment = parent;
         }
+        environment
     }
 }
 
@@ -75,7 +76,7 @@ mod environment_tests {
     use std::{cell::RefCell, rc::Rc};
 
     use crate::{
-        ast::name::Name,
+        ast::name::Slot,
         interpreter::{environment::Environment, value::Value},
     };
 
@@ -91,42 +92,39 @@ mod environment_tests {
     #[test]
     fn define_get() {
         let mut env = Environment::new();
-        let name = "x".into();
-        env.define(&name, Value::Boolean(true));
-        let returned = env.get(&name);
+        env.define(0, Value::Boolean(true));
+        let returned = env.get_at(Slot::new(0, 0));
         assert_eq!(returned, Some(Value::Boolean(true)))
     }
 
     #[test]
     fn define_assign_get() {
         let mut env = Environment::new();
-        let name = "x".into();
-        env.define(&name, Value::Boolean(true));
-        let assigned = env.assign(&name, &Value::Boolean(false));
+        env.define(0, Value::Nil);
+        env.define(1, Value::Boolean(true));
+        let assigned = env.assign_at(Slot::new(0, 1), &Value::Boolean(false));
         assert!(assigned);
-        let returned = env.get(&name);
+        let returned = env.get_at(Slot::new(0, 1));
         assert_eq!(returned, Some(Value::Boolean(false)))
     }
 
     #[test]
     fn assign_unasigned() {
         let mut env = Environment::new();
-        let name = "x".into();
-        let assigned = env.assign(&name, &Value::Boolean(false));
+        let assigned = env.assign_at(Slot::new(0, 0), &Value::Boolean(false));
         assert!(!assigned);
-        let returned = env.get(&name);
+        let returned = env.get_at(Slot::new(0, 0));
         assert_eq!(returned, None)
     }
 
     #[test]
     fn assign_and_get_from_depth() {
-        let name: Name = "a".into();
         let value = Value::Boolean(true);
         let mut top = Environment::new();
-        top.define(&name, Value::Nil);
+        top.define(0, Value::Nil);
         let mut env = create_depth(8, top);
-        env.assign_at(7, &name, &value);
-        let ret = env.get_at(7, &name).unwrap();
+        env.assign_at(Slot::new(7, 0), &value);
+        let ret = env.get_at(Slot::new(7, 0)).unwrap();
         assert_eq!(value, ret)
     }
 }
@@ -3,18 +3,29 @@ use miette::SourceSpan;
 use crate::{
     ast::{
         expr::{Expr, ExprType},
-        name::{Name, NameExpr},
+        name::{NameExpr, Slot},
         token::{Token, TokenType},
     },
     interpreter::runtime_error::RuntimeError,
 };
 
 use super::{
-    callable::Callable, literal::LiteralInterpreter, runtime_error::RuntimeError::*, types::Type,
+    call_stack::Frame,
+    callable::Callable,
+    list::List,
+    literal::LiteralInterpreter,
+    map::{Map, MapKey},
+    native_functions::CallSite,
+    runtime_error::RuntimeError::*,
+    types::Type,
     value::Value,
 };
 use super::{Interpreter, Result};
 
+/// Stack left before a call switches to a new segment, enough for one Lox call in debug builds.
+const RED_ZONE: usize = 256 * 1024;
+const STACK_SEGMENT: usize = 4 * 1024 * 1024;
+
 impl Interpreter {
     pub fn interpret_expr(&mut self, expr: &Expr) -> Result<Value> {
         use ExprType::*;
@@ -30,11 +41,118 @@ impl Interpreter {
             Call(callee, arguments) => self.call(callee, arguments, expr.location),
             Get(object, name) => self.get(object, name, location),
             Set(object, name, value) => self.set(object, name, value, location),
-            This => self.read_variable(&NameExpr::this(location, expr.src.clone())),
-            Super(method) => self.interpret_super(method, location),
+            This(this) => self.read_variable(this),
+            Super(keyword, method) => self.interpret_super(keyword, method),
+            List(elements) => self.list(elements),
+            Interpolation(parts) => self.interpolation(parts),
+            Map(entries) => self.map(entries),
+            Index(object, index) => self.index(object, index),
+            SetIndex(object, index, value) => self.set_index(object, index, value),
+        }
+    }
+
+    fn list(&mut self, elements: &[Expr]) -> Result<Value> {
+        let elements = elements
+            .iter()
+            .map(|e| self.interpret_expr(e))
+            .collect::<Result<_>>()?;
+        Ok(Value::List(List::new(elements)))
+    }
+
+    fn interpolation(&mut self, parts: &[Expr]) -> Result<Value> {
+        let string = parts
+            .iter()
+            .map(|part| self.interpret_expr(part).map(|value| value.to_string()))
+            .collect::<Result<String>>()?;
+        Ok(Value::String(string))
+    }
+
+    fn map(&mut self, entries: &[(Expr, Expr)]) -> Result<Value> {
+        let entries = entries
+            .iter()
+            .map(|(key, value)| Ok((self.map_key(key)?, self.interpret_expr(value)?)))
+            .collect::<Result<_>>()?;
+        Ok(Value::Map(Map::new(entries)))
+    }
+
+    fn index(&mut self, object: &Expr, index: &Expr) -> Result<Value> {
+        match self.interpret_expr(object)? {
+            Value::List(list) => {
+                let index = self.list_index(&list, index)?;
+                Ok(list.get(index).expect("bounds checked in list_index"))
+            }
+            Value::Map(map) => {
+                let key = self.map_key(index)?;
+                map.get(&key).ok_or(UndefinedKey {
+                    key: Value::from(key).to_string(),
+                    src: index.src.clone(),
+                    location: index.location,
+                })
+            }
+            value => Err(NotIndexable {
+                actual: value.get_type(),
+                src: object.src.clone(),
+                location: object.location,
+            }),
+        }
+    }
+
+    fn set_index(&mut self, object: &Expr, index: &Expr, value: &Expr) -> Result<Value> {
+        match self.interpret_expr(object)? {
+            Value::List(list) => {
+                let index = self.list_index(&list, index)?;
+                let value = self.interpret_expr(value)?;
+                list.set(index, value.clone());
+                Ok(value)
+            }
+            Value::Map(map) => {
+                let key = self.map_key(index)?;
+                let value = self.interpret_expr(value)?;
+                map.insert(key, value.clone());
+                Ok(value)
+            }
+            value => Err(NotIndexable {
+                actual: value.get_type(),
+                src: object.src.clone(),
+                location: object.location,
+            }),
         }
     }
 
+    fn list_index(&mut self, list: &List, index: &Expr) -> Result<usize> {
+        let number = match self.interpret_expr(index)? {
+            Value::Number(n) if n.fract() == 0.0 => n,
+            Value::Number(n) => Err(NonIntegerIndex {
+                actual: n.to_string(),
+                src: index.src.clone(),
+                location: index.location,
+            })?,
+            value => Err(NonIntegerIndex {
+                actual: value.get_type().to_string(),
+                src: index.src.clone(),
+                location: index.location,
+            })?,
+        };
+        if number < 0.0 || number >= list.len() as f64 {
+            Err(IndexOutOfBounds {
+                index: number,
+                length: list.len(),
+                src: index.src.clone(),
+                location: index.location,
+            })
+        } else {
+            Ok(number as usize)
+        }
+    }
+
+    fn map_key(&mut self, key: &Expr) -> Result<MapKey> {
+        MapKey::try_from(self.interpret_expr(key)?).map_err(|actual| UnhashableKey {
+            actual,
+            src: key.src.clone(),
+            location: key.location,
+        })
+    }
+
     fn get(&mut self, object: &Expr, name_expr: &NameExpr, location: SourceSpan) -> Result<Value> {
         let object = self.interpret_expr(object)?;
         if let Value::Instance(instance) = object {
@@ -74,10 +192,9 @@ impl Interpreter {
     }
 
     pub fn read_variable(&self, name: &NameExpr) -> Result<Value> {
-        let val = if let Some(distance) = self.locals.get(name) {
-            self.environment.borrow().get_at(*distance, &name.name)
-        } else {
-            self.global.borrow().get(&name.name)
+        let val = match name.slot {
+            Some(slot) => self.environment.borrow().get_at(slot),
+            None => self.globals.get(&name.name).cloned(),
         };
         val.ok_or(UndefinedVariable {
             name: name.name.clone(),
@@ -88,12 +205,13 @@ impl Interpreter {
 
     fn assign_variable(&mut self, name: &NameExpr, expr: &Expr) -> Result<Value> {
         let value = self.interpret_expr(expr)?;
-        let result = if let Some(distance) = self.locals.get(name) {
-            self.environment
-                .borrow_mut()
-                .assign_at(*distance, &name.name, &value)
-        } else {
-            self.global.borrow_mut().assign(&name.name, &value)
+        let result = match name.slot {
+            Some(slot) => self.environment.borrow_mut().assign_at(slot, &value),
+            None => self
+                .globals
+                .get_mut(&name.name)
+                .map(|old| *old = value.clone())
+                .is_some(),
         };
         if result {
             Ok(value)
@@ -121,7 +239,25 @@ impl Interpreter {
                     .iter()
                     .map(|a| self.interpret_expr(a))
                     .collect::<Result<_>>()?;
-                callable.call(self, args)
+                self.check_deadline(&callee.src, location)?;
+                self.check_call_depth(&callee.src, location)?;
+                let call_site = CallSite {
+                    src: &callee.src,
+                    location,
+                    arguments,
+                };
+                self.call_stack.push(Frame {
+                    function: callable.name(),
+                    src: callee.src.clone(),
+                    location,
+                });
+                // deep recursion continues on a fresh stack segment instead of overflowing
+                let result = stacker::maybe_grow(RED_ZONE, STACK_SEGMENT, || {
+                    callable.call(self, args, &call_site)
+                });
+                let result = result.map_err(|err| err.with_trace(&self.call_stack));
+                self.call_stack.pop();
+                result
             }
         } else {
             Err(CallingNonCallable {
@@ -132,19 +268,15 @@ impl Interpreter {
         }
     }
 
-    fn interpret_super(&mut self, method_expr: &NameExpr, location: SourceSpan) -> Result<Value> {
-        let distance = self
-            .locals
-            .get(&NameExpr {
-                name: Name::super_name(),
-                location,
-                src: method_expr.src.clone(),
-            })
+    /// `super` is alone in the environment around the one binding `this`.
+    fn interpret_super(&mut self, keyword: &NameExpr, method_expr: &NameExpr) -> Result<Value> {
+        let slot = keyword
+            .slot
             .expect("super local was undefined bug in resolver");
         let superclass = self
             .environment
             .borrow()
-            .get_at(*distance, &Name::super_name())
+            .get_at(slot)
             .expect("super value not in environment: bug in interpreter");
         let superclass = if let Value::Callable(Callable::Class(class)) = superclass {
             class
@@ -154,7 +286,7 @@ impl Interpreter {
         let object = self
             .environment
             .borrow()
-            .get_at(distance - 1, &Name::this())
+            .get_at(Slot::new(slot.depth - 1, 0))
             .expect("object in super call was not in environment: bug in interpreter");
         let object = if let Value::Instance(instance) = object {
             instance
@@ -313,8 +445,8 @@ mod value_interpreter_tests {
             token::{Token, TokenType},
         },
         interpreter::{
-            environment::Environment, printer::vec_printer::VecPrinter,
-            runtime_error::RuntimeError::*, types::Type, value::Value, Interpreter,
+            printer::VecPrinter, runtime_error::RuntimeError::*, types::Type, value::Value,
+            Interpreter,
         },
     };
 
@@ -483,9 +615,8 @@ mod value_interpreter_tests {
         let name: Name = "a".into();
         let right = literal(false.into());
         let expr = Expr::assign(name_expr(name.clone()), right);
-        let mut env = Environment::new();
-        env.define(&name, Value::Nil);
-        let mut under_test = Interpreter::with_env(Box::new(VecPrinter::new()), env);
+        let mut under_test = Interpreter::from_printer(Box::new(VecPrinter::new()));
+        under_test.globals.insert(name, Value::Nil);
         assert_matches!(
             under_test.interpret_expr(&expr).unwrap(),
             Value::Boolean(false)
@@ -504,10 +635,10 @@ mod value_interpreter_tests {
         Expr::literal(literal, &token(TokenType::Eof))
     }
     fn name_expr(name: Name) -> NameExpr {
-        NameExpr {
+        NameExpr::new(
             name,
-            location: (0, 1).into(),
-            src: NamedSource::new("name", String::new()).into(),
-        }
+            (0, 1).into(),
+            NamedSource::new("name", String::new()).into(),
+        )
     }
 }
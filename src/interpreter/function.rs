@@ -1,12 +1,18 @@
 use std::{cell::RefCell, fmt::Display, rc::Rc};
 
-use crate::ast::{name::Name, stmt::Stmt};
+use crate::ast::{
+    name::{Name, Slot},
+    stmt::Stmt,
+};
 
 use super::{
     class::Instance, environment::Environment, runtime_error::RuntimeErrorOrReturn, value::Value,
     Interpreter, Result,
 };
 
+/// Where a bound method finds `this`, alone in the environment around its closure.
+const THIS: Slot = Slot { depth: 0, index: 0 };
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Function {
     name: Name,
@@ -34,36 +40,37 @@ impl Function {
     }
 
     pub fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value> {
+        // the parameters take the first slots, the locals of the body follow
         let mut env = Environment::from_parent(self.closure.clone());
-        self.parameters
-            .iter()
-            .zip(arguments.iter())
-            .for_each(|(p, a)| env.define(p, a.clone()));
+        arguments
+            .into_iter()
+            .enumerate()
+            .for_each(|(index, argument)| env.define(index, argument));
         let result = interpreter.execute_block(&self.body, env);
         match result {
-            Ok(_) if self.is_initializer => Ok(self
-                .closure
-                .borrow()
-                .get_at(0, &Name::this())
-                .unwrap_or(Value::Nil)),
+            Ok(_) if self.is_initializer => {
+                Ok(self.closure.borrow().get_at(THIS).unwrap_or(Value::Nil))
+            }
             Ok(_) => Ok(Value::Nil),
-            Err(RuntimeErrorOrReturn::Return(_)) if self.is_initializer => Ok(self
-                .closure
-                .borrow()
-                .get_at(0, &Name::this())
-                .unwrap_or(Value::Nil)),
+            Err(RuntimeErrorOrReturn::Return(_)) if self.is_initializer => {
+                Ok(self.closure.borrow().get_at(THIS).unwrap_or(Value::Nil))
+            }
             Err(RuntimeErrorOrReturn::Return(value)) => Ok(value),
-            Err(RuntimeErrorOrReturn::RuntimeError(err)) => Err(err),
+            Err(err) => Err(err.unwrap_runtime_error()),
         }
     }
 
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
     pub fn arity(&self) -> usize {
         self.parameters.len()
     }
 
     pub fn bind(self, instance: &Instance) -> Self {
         let mut env = Environment::from_parent(self.closure.clone());
-        env.define(&Name::this(), Value::Instance(instance.clone()));
+        env.define(0, Value::Instance(instance.clone()));
         //TODO: ahhhh, instances are not clonable
         Self {
             name: self.name,
@@ -0,0 +1,98 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use miette::{NamedSource, SourceSpan};
+
+use crate::ast::stmt::Stmt;
+
+use super::{runtime_error::RuntimeError::*, Interpreter, Result};
+
+/// Bounds for running untrusted programs, statements and time are counted per run. Only the
+/// call depth is limited by default. Limits are checked before each statement, the timeout
+/// also before each call, so a single native call can still run past it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits {
+    pub max_statements: Option<u64>,
+    /// Nesting of calls to functions, classes and natives.
+    pub max_call_depth: Option<usize>,
+    pub timeout: Option<Duration>,
+}
+
+impl Limits {
+    /// Deep enough for ordinary recursion, calls grow the Rust stack on the heap as needed so
+    /// this does not depend on the stack size of the calling thread.
+    pub const DEFAULT_MAX_CALL_DEPTH: usize = 10_000;
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_statements: None,
+            max_call_depth: Some(Self::DEFAULT_MAX_CALL_DEPTH),
+            timeout: None,
+        }
+    }
+}
+
+impl Interpreter {
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits
+    }
+
+    pub(super) fn start_run(&mut self) {
+        self.executed_statements = 0;
+        self.deadline = self.limits.timeout.map(|timeout| Instant::now() + timeout);
+    }
+
+    pub(super) fn check_limits(&mut self, statement: &Stmt) -> Result<()> {
+        self.executed_statements += 1;
+        if let Some(limit) = self.limits.max_statements {
+            if self.executed_statements > limit {
+                return Err(StepLimitExceeded {
+                    limit,
+                    src: statement.src.clone(),
+                    location: statement.location,
+                });
+            }
+        }
+        self.check_deadline(&statement.src, statement.location)
+    }
+
+    /// Checked before every call pushes its frame, natives and classes included.
+    pub(super) fn check_call_depth(
+        &self,
+        src: &Arc<NamedSource<String>>,
+        location: SourceSpan,
+    ) -> Result<()> {
+        if let Some(limit) = self.limits.max_call_depth {
+            if self.call_stack.len() >= limit {
+                return Err(StackOverflow {
+                    limit,
+                    src: src.clone(),
+                    location,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Also checked on calls, an expression can call many functions in one statement.
+    pub(super) fn check_deadline(
+        &self,
+        src: &Arc<NamedSource<String>>,
+        location: SourceSpan,
+    ) -> Result<()> {
+        if let (Some(deadline), Some(timeout)) = (self.deadline, self.limits.timeout) {
+            if Instant::now() > deadline {
+                return Err(Timeout {
+                    timeout,
+                    src: src.clone(),
+                    location,
+                });
+            }
+        }
+        Ok(())
+    }
+}
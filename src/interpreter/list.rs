@@ -0,0 +1,85 @@
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
+use super::value::{Printing, Value};
+
+#[derive(Debug, Clone)]
+pub struct List {
+    elements: Rc<RefCell<Vec<Value>>>,
+}
+
+impl List {
+    pub fn new(elements: Vec<Value>) -> Self {
+        Self {
+            elements: Rc::new(RefCell::new(elements)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.borrow().len()
+    }
+
+    pub fn elements(&self) -> Vec<Value> {
+        self.elements.borrow().clone()
+    }
+
+    pub fn position(&self, value: &Value) -> Option<usize> {
+        self.elements.borrow().iter().position(|e| e == value)
+    }
+
+    pub fn get(&self, index: usize) -> Option<Value> {
+        self.elements.borrow().get(index).cloned()
+    }
+
+    pub fn push(&self, value: Value) {
+        self.elements.borrow_mut().push(value)
+    }
+
+    // copies the elements first so a list can be appended to itself
+    pub fn extend(&self, other: &List) {
+        let elements = other.elements();
+        self.elements.borrow_mut().extend(elements)
+    }
+
+    pub fn set(&self, index: usize, value: Value) -> bool {
+        self.elements
+            .borrow_mut()
+            .get_mut(index)
+            .map(|old| *old = value)
+            .is_some()
+    }
+}
+
+// lists are reference values: two lists are only equal if they are the same list
+impl PartialEq for List {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.elements, &other.elements)
+    }
+}
+
+impl List {
+    pub(super) fn fmt_nested(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        printing: &mut Printing,
+    ) -> std::fmt::Result {
+        let id = Rc::as_ptr(&self.elements) as *const ();
+        if !printing.insert(id) {
+            return write!(f, "[...]");
+        }
+        write!(f, "[")?;
+        for (i, element) in self.elements.borrow().iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            element.fmt_nested(f, printing)?;
+        }
+        printing.remove(&id);
+        write!(f, "]")
+    }
+}
+
+impl Display for List {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_nested(f, &mut Printing::new())
+    }
+}
@@ -0,0 +1,125 @@
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
+use indexmap::IndexMap;
+
+use super::{
+    types::Type,
+    value::{Printing, Value},
+};
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    entries: Rc<RefCell<IndexMap<MapKey, Value>>>,
+}
+
+impl Map {
+    pub fn new(entries: IndexMap<MapKey, Value>) -> Self {
+        Self {
+            entries: Rc::new(RefCell::new(entries)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn get(&self, key: &MapKey) -> Option<Value> {
+        self.entries.borrow().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: MapKey, value: Value) {
+        self.entries.borrow_mut().insert(key, value);
+    }
+
+    pub fn contains_key(&self, key: &MapKey) -> bool {
+        self.entries.borrow().contains_key(key)
+    }
+
+    pub fn remove(&self, key: &MapKey) -> Option<Value> {
+        self.entries.borrow_mut().shift_remove(key)
+    }
+
+    pub fn keys(&self) -> Vec<Value> {
+        self.entries
+            .borrow()
+            .keys()
+            .cloned()
+            .map(Value::from)
+            .collect()
+    }
+
+    pub fn values(&self) -> Vec<Value> {
+        self.entries.borrow().values().cloned().collect()
+    }
+}
+
+// maps are reference values: two maps are only equal if they are the same map
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.entries, &other.entries)
+    }
+}
+
+impl Map {
+    pub(super) fn fmt_nested(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        printing: &mut Printing,
+    ) -> std::fmt::Result {
+        let id = Rc::as_ptr(&self.entries) as *const ();
+        if !printing.insert(id) {
+            return write!(f, "{{...}}");
+        }
+        write!(f, "{{")?;
+        for (i, (key, value)) in self.entries.borrow().iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: ", Value::from(key.clone()))?;
+            value.fmt_nested(f, printing)?;
+        }
+        printing.remove(&id);
+        write!(f, "}}")
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_nested(f, &mut Printing::new())
+    }
+}
+
+/// The subset of values that can be used as map keys.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MapKey {
+    String(String),
+    Number(u64), // bits of the f64, with -0 normalized to 0
+    Boolean(bool),
+    Nil,
+}
+
+impl TryFrom<Value> for MapKey {
+    type Error = Type;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(s) => Ok(MapKey::String(s)),
+            Value::Number(0.0) => Ok(MapKey::Number(0.0f64.to_bits())), // also matches -0
+            Value::Number(n) => Ok(MapKey::Number(n.to_bits())),
+            Value::Boolean(b) => Ok(MapKey::Boolean(b)),
+            Value::Nil => Ok(MapKey::Nil),
+            value => Err(value.get_type()),
+        }
+    }
+}
+
+impl From<MapKey> for Value {
+    fn from(value: MapKey) -> Self {
+        match value {
+            MapKey::String(s) => Value::String(s),
+            MapKey::Number(bits) => Value::Number(f64::from_bits(bits)),
+            MapKey::Boolean(b) => Value::Boolean(b),
+            MapKey::Nil => Value::Nil,
+        }
+    }
+}
@@ -1,73 +1,115 @@
+pub mod call_stack;
 mod callable;
 mod class;
 mod environment;
 mod expression;
 mod function;
+pub mod limits;
+mod list;
 mod literal;
+mod map;
 mod native_functions;
 pub mod printer;
 pub mod runtime_error;
 mod statement;
-mod types;
+pub mod types;
 pub mod value;
 
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Instant};
 
-use crate::ast::{name::NameExpr, stmt::Stmt};
+use crate::ast::{
+    expr::Expr,
+    name::{Name, NameExpr},
+    stmt::Stmt,
+};
 
 use self::{
+    call_stack::Frame,
+    callable::Callable,
     environment::Environment,
+    limits::Limits,
+    native_functions::{native_functions, Native},
     printer::{ConsolePrinter, Printer},
     runtime_error::{RuntimeError, RuntimeErrorOrReturn},
+    value::Value,
 };
 
-type Result<T> = std::result::Result<T, RuntimeError>;
+pub type Result<T> = std::result::Result<T, RuntimeError>;
 type OrReturnResult<T> = std::result::Result<T, RuntimeErrorOrReturn>;
 pub struct Interpreter {
     printer: Box<dyn Printer>,
+    /// The innermost local scope, an empty one at the top level.
     environment: Rc<RefCell<Environment>>,
-    global: Rc<RefCell<Environment>>,
-    locals: HashMap<NameExpr, usize>,
+    globals: HashMap<Name, Value>,
+    limits: Limits,
+    executed_statements: u64,
+    call_stack: Vec<Frame>,
+    deadline: Option<Instant>,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Interpreter {
     pub fn new() -> Self {
-        let global = Rc::new(RefCell::new(Environment::with_native_functions()));
+        Self::from_printer(Box::new(ConsolePrinter))
+    }
+
+    /// Creates an interpreter that hands everything `print`ed to `printer`.
+    pub fn from_printer(printer: Box<dyn Printer>) -> Self {
+        let globals = native_functions()
+            .into_iter()
+            .map(|(name, native)| (name, Value::Callable(Callable::Native(native))))
+            .collect();
         Self {
-            printer: Box::new(ConsolePrinter),
-            environment: global.clone(),
-            global,
-            locals: HashMap::new(),
+            printer,
+            environment: Rc::new(RefCell::new(Environment::new())),
+            globals,
+            limits: Limits::default(),
+            executed_statements: 0,
+            call_stack: vec![],
+            deadline: None,
         }
     }
 
     pub fn interpret(&mut self, statements: &[Stmt]) -> Result<()> {
+        self.start_run();
         let ret = statements.iter().try_for_each(|s| self.interpret_stmt(s));
         ret.map_err(|err| err.unwrap_runtime_error())
     }
 
-    pub fn add_locals(&mut self, locals: HashMap<NameExpr, usize>) {
-        self.locals.extend(locals)
+    /// Evaluates a single expression as its own run, e.g. a line typed into the REPL.
+    pub fn evaluate(&mut self, expr: &Expr) -> Result<Value> {
+        self.start_run();
+        self.interpret_expr(expr)
     }
-    #[cfg(test)]
-    pub fn from_printer(printer: Box<dyn Printer>) -> Self {
-        let global = Rc::new(RefCell::new(Environment::new()));
-        Self {
-            printer,
-            environment: global.clone(),
-            global,
-            locals: HashMap::new(),
-        }
+
+    pub fn register_native(
+        &mut self,
+        name: &str,
+        arity: usize,
+        function: impl Fn(&mut Interpreter, Vec<Value>) -> Result<Value> + 'static,
+    ) {
+        let native = Native::from_host(name, arity, function);
+        self.globals
+            .insert(name.into(), Value::Callable(Callable::Native(native)));
     }
 
-    #[cfg(test)]
-    pub fn with_env(printer: Box<dyn Printer>, environment: Environment) -> Self {
-        let global = Rc::new(RefCell::new(environment));
-        Self {
-            printer,
-            environment: global.clone(),
-            global,
-            locals: HashMap::new(),
+    /// Names defined in the global environment, natives included.
+    pub fn global_names(&self) -> Vec<Name> {
+        self.globals.keys().cloned().collect()
+    }
+
+    /// Declarations resolved to a slot are local, the others global.
+    fn define(&mut self, name: &NameExpr, value: Value) {
+        match name.slot {
+            Some(slot) => self.environment.borrow_mut().define(slot.index, value),
+            None => {
+                self.globals.insert(name.name.clone(), value);
+            }
         }
     }
 }
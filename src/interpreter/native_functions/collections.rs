@@ -0,0 +1,126 @@
+use super::{
+    expect_key, expect_list, expect_map, expect_string, native_argument_one_of, CallSite,
+    Interpreter, List, Result, Value,
+};
+use crate::interpreter::types::Type;
+
+pub(super) fn len(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let length = match &arguments[0] {
+        Value::String(s) => s.chars().count(),
+        Value::List(list) => list.len(),
+        Value::Map(map) => map.len(),
+        _ => Err(native_argument_one_of(
+            "len",
+            &[Type::String, Type::List, Type::Map],
+            &arguments,
+            0,
+            call_site,
+        ))?,
+    };
+    Ok(Value::Number(length as f64))
+}
+
+// position of the first occurrence in a string or list, -1 if there is none
+pub(super) fn index_of(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let index = match &arguments[0] {
+        Value::String(haystack) => {
+            let needle = expect_string("index_of", &arguments, 1, call_site)?;
+            haystack
+                .find(needle)
+                .map(|byte_index| haystack[..byte_index].chars().count())
+        }
+        Value::List(list) => list.position(&arguments[1]),
+        _ => Err(native_argument_one_of(
+            "index_of",
+            &[Type::String, Type::List],
+            &arguments,
+            0,
+            call_site,
+        ))?,
+    };
+    Ok(Value::Number(index.map_or(-1.0, |i| i as f64)))
+}
+
+pub(super) fn join(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let list = expect_list("join", &arguments, 0, call_site)?;
+    let separator = expect_string("join", &arguments, 1, call_site)?;
+    let joined = list
+        .elements()
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join(separator);
+    Ok(Value::String(joined))
+}
+
+pub(super) fn push(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let list = expect_list("push", &arguments, 0, call_site)?;
+    list.push(arguments[1].clone());
+    Ok(Value::Nil)
+}
+
+// adds every element of the second list to the end of the first
+pub(super) fn append(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let list = expect_list("append", &arguments, 0, call_site)?;
+    let other = expect_list("append", &arguments, 1, call_site)?;
+    list.extend(&other);
+    Ok(Value::Nil)
+}
+
+pub(super) fn keys(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let map = expect_map("keys", &arguments, 0, call_site)?;
+    Ok(Value::List(List::new(map.keys())))
+}
+
+pub(super) fn values(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let map = expect_map("values", &arguments, 0, call_site)?;
+    Ok(Value::List(List::new(map.values())))
+}
+
+pub(super) fn has(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let map = expect_map("has", &arguments, 0, call_site)?;
+    let key = expect_key(&arguments, 1, call_site)?;
+    Ok(Value::Boolean(map.contains_key(&key)))
+}
+
+pub(super) fn remove(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let map = expect_map("remove", &arguments, 0, call_site)?;
+    let key = expect_key(&arguments, 1, call_site)?;
+    Ok(map.remove(&key).unwrap_or(Value::Nil))
+}
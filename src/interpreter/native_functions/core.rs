@@ -0,0 +1,55 @@
+use std::{io::stdin, time::SystemTime};
+
+use super::{expect_string, CallSite, Interpreter, Result, RuntimeError::*, Value};
+
+pub(super) fn clock(_: &mut Interpreter, _: Vec<Value>, _: &CallSite) -> Result<Value> {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap();
+    Ok(Value::Number(now.as_secs_f64()))
+}
+
+pub(super) fn type_of(_: &mut Interpreter, arguments: Vec<Value>, _: &CallSite) -> Result<Value> {
+    Ok(Value::String(arguments[0].get_type().to_string()))
+}
+
+pub(super) fn str(_: &mut Interpreter, arguments: Vec<Value>, _: &CallSite) -> Result<Value> {
+    Ok(Value::String(arguments[0].to_string()))
+}
+
+pub(super) fn num(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let string = expect_string("num", &arguments, 0, call_site)?;
+    // Rust also parses "inf" and "NaN", which are no Lox number literals
+    string
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .map(Value::Number)
+        .ok_or_else(|| NotANumber {
+            input: string.to_string(),
+            src: call_site.src.clone(),
+            location: call_site.argument_location(0),
+        })
+}
+
+// returns nil once stdin is exhausted
+pub(super) fn input(_: &mut Interpreter, _: Vec<Value>, call_site: &CallSite) -> Result<Value> {
+    let mut line = String::new();
+    let read = stdin().read_line(&mut line).map_err(|err| InputError {
+        message: err.to_string(),
+        src: call_site.src.clone(),
+        location: call_site.location,
+    })?;
+    if read == 0 {
+        Ok(Value::Nil)
+    } else {
+        let line = line.strip_suffix('\n').unwrap_or(&line);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        Ok(Value::String(line.to_string()))
+    }
+}
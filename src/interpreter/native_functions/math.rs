@@ -0,0 +1,47 @@
+use super::{expect_number, CallSite, Interpreter, Result, Value};
+
+pub(super) fn floor(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let n = expect_number("floor", &arguments, 0, call_site)?;
+    Ok(Value::Number(n.floor()))
+}
+
+pub(super) fn ceil(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let n = expect_number("ceil", &arguments, 0, call_site)?;
+    Ok(Value::Number(n.ceil()))
+}
+
+pub(super) fn sqrt(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let n = expect_number("sqrt", &arguments, 0, call_site)?;
+    Ok(Value::Number(n.sqrt()))
+}
+
+pub(super) fn pow(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let base = expect_number("pow", &arguments, 0, call_site)?;
+    let exponent = expect_number("pow", &arguments, 1, call_site)?;
+    Ok(Value::Number(base.powf(exponent)))
+}
+
+pub(super) fn abs(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let n = expect_number("abs", &arguments, 0, call_site)?;
+    Ok(Value::Number(n.abs()))
+}
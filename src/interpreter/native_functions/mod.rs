@@ -0,0 +1,365 @@
+mod collections;
+mod core;
+mod math;
+mod string;
+
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    rc::Rc,
+    sync::Arc,
+};
+
+use miette::{NamedSource, SourceSpan};
+
+use crate::ast::{expr::Expr, name::Name};
+
+use super::{
+    list::List,
+    map::{Map, MapKey},
+    runtime_error::RuntimeError::{self, *},
+    types::Type,
+    value::Value,
+    Interpreter, Result,
+};
+
+type NativeFunction = dyn Fn(&mut Interpreter, Vec<Value>, &CallSite) -> Result<Value>;
+
+#[derive(Clone)]
+pub struct Native {
+    function: Rc<NativeFunction>,
+    arity: usize,
+    name: String,
+}
+
+/// The call expression a native is invoked from, so it can label errors about its arguments.
+pub struct CallSite<'a> {
+    pub src: &'a Arc<NamedSource<String>>,
+    pub location: SourceSpan,
+    pub arguments: &'a [Expr],
+}
+
+impl CallSite<'_> {
+    pub fn argument_location(&self, index: usize) -> SourceSpan {
+        self.arguments[index].location
+    }
+}
+
+impl Native {
+    pub fn new(
+        name: &str,
+        arity: usize,
+        function: impl Fn(&mut Interpreter, Vec<Value>, &CallSite) -> Result<Value> + 'static,
+    ) -> Self {
+        Self {
+            function: Rc::new(function),
+            arity,
+            name: name.to_string(),
+        }
+    }
+
+    /// Wraps a host function that does not know about call sites, errors created with
+    /// [`RuntimeError::native_failure`] are labelled with the call they happened in.
+    pub fn from_host(
+        name: &str,
+        arity: usize,
+        function: impl Fn(&mut Interpreter, Vec<Value>) -> Result<Value> + 'static,
+    ) -> Self {
+        Self::new(name, arity, move |interpreter, arguments, call_site| {
+            function(interpreter, arguments).map_err(|err| match err {
+                NativeFailure {
+                    message,
+                    src: None,
+                    location: None,
+                } => NativeFailure {
+                    message,
+                    src: Some(call_site.src.clone()),
+                    location: Some(call_site.location),
+                },
+                err => err,
+            })
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    pub fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+        call_site: &CallSite,
+    ) -> Result<Value> {
+        (self.function)(interpreter, arguments, call_site)
+    }
+}
+
+impl PartialEq for Native {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && Rc::ptr_eq(&self.function, &other.function)
+    }
+}
+
+impl Debug for Native {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Native")
+            .field("name", &self.name)
+            .field("arity", &self.arity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for Native {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<native fun {} ({} arguments)>", self.name, self.arity)
+    }
+}
+
+pub fn native_functions() -> HashMap<Name, Native> {
+    let mut builtins = HashMap::new();
+    insert(&mut builtins, "clock", 0, core::clock);
+    insert(&mut builtins, "type_of", 1, core::type_of);
+    insert(&mut builtins, "str", 1, core::str);
+    insert(&mut builtins, "num", 1, core::num);
+    insert(&mut builtins, "input", 0, core::input);
+
+    insert(&mut builtins, "floor", 1, math::floor);
+    insert(&mut builtins, "ceil", 1, math::ceil);
+    insert(&mut builtins, "sqrt", 1, math::sqrt);
+    insert(&mut builtins, "pow", 2, math::pow);
+    insert(&mut builtins, "abs", 1, math::abs);
+
+    insert(&mut builtins, "substr", 3, string::substr);
+    insert(&mut builtins, "upper", 1, string::upper);
+    insert(&mut builtins, "lower", 1, string::lower);
+    insert(&mut builtins, "split", 2, string::split);
+
+    insert(&mut builtins, "len", 1, collections::len);
+    insert(&mut builtins, "index_of", 2, collections::index_of);
+    insert(&mut builtins, "join", 2, collections::join);
+    insert(&mut builtins, "push", 2, collections::push);
+    insert(&mut builtins, "append", 2, collections::append);
+    insert(&mut builtins, "keys", 1, collections::keys);
+    insert(&mut builtins, "values", 1, collections::values);
+    insert(&mut builtins, "has", 2, collections::has);
+    insert(&mut builtins, "remove", 2, collections::remove);
+    builtins
+}
+
+fn insert(
+    builtins: &mut HashMap<Name, Native>,
+    name: &str,
+    arity: usize,
+    function: fn(&mut Interpreter, Vec<Value>, &CallSite) -> Result<Value>,
+) {
+    builtins.insert(name.into(), Native::new(name, arity, function));
+}
+
+fn expect_number(
+    function: &str,
+    arguments: &[Value],
+    index: usize,
+    call_site: &CallSite,
+) -> Result<f64> {
+    if let Value::Number(n) = &arguments[index] {
+        Ok(*n)
+    } else {
+        Err(native_argument(
+            function,
+            Type::Number,
+            arguments,
+            index,
+            call_site,
+        ))
+    }
+}
+
+fn expect_integer(
+    function: &str,
+    arguments: &[Value],
+    index: usize,
+    call_site: &CallSite,
+) -> Result<i64> {
+    let n = expect_number(function, arguments, index, call_site)?;
+    if n.fract() == 0.0 {
+        Ok(n as i64)
+    } else {
+        Err(NonIntegerArgument {
+            function: function.to_string(),
+            position: index + 1,
+            actual: n.to_string(),
+            src: call_site.src.clone(),
+            call_location: call_site.location,
+            argument_location: call_site.argument_location(index),
+        })
+    }
+}
+
+fn expect_string<'a>(
+    function: &str,
+    arguments: &'a [Value],
+    index: usize,
+    call_site: &CallSite,
+) -> Result<&'a str> {
+    if let Value::String(s) = &arguments[index] {
+        Ok(s)
+    } else {
+        Err(native_argument(
+            function,
+            Type::String,
+            arguments,
+            index,
+            call_site,
+        ))
+    }
+}
+
+fn expect_list(
+    function: &str,
+    arguments: &[Value],
+    index: usize,
+    call_site: &CallSite,
+) -> Result<List> {
+    if let Value::List(list) = &arguments[index] {
+        Ok(list.clone())
+    } else {
+        Err(native_argument(
+            function,
+            Type::List,
+            arguments,
+            index,
+            call_site,
+        ))
+    }
+}
+
+fn expect_map(
+    function: &str,
+    arguments: &[Value],
+    index: usize,
+    call_site: &CallSite,
+) -> Result<Map> {
+    if let Value::Map(map) = &arguments[index] {
+        Ok(map.clone())
+    } else {
+        Err(native_argument(
+            function,
+            Type::Map,
+            arguments,
+            index,
+            call_site,
+        ))
+    }
+}
+
+fn expect_key(arguments: &[Value], index: usize, call_site: &CallSite) -> Result<MapKey> {
+    MapKey::try_from(arguments[index].clone()).map_err(|actual| UnhashableKey {
+        actual,
+        src: call_site.src.clone(),
+        location: call_site.argument_location(index),
+    })
+}
+
+fn native_argument_one_of(
+    function: &str,
+    expected: &[Type],
+    arguments: &[Value],
+    index: usize,
+    call_site: &CallSite,
+) -> RuntimeError {
+    match expected {
+        [] => panic!("{function} must accept at least one type"),
+        [only] => native_argument(function, *only, arguments, index, call_site),
+        [others @ .., last] => {
+            let others = others.iter().map(Type::to_string).collect::<Vec<_>>();
+            NativeArgumentOneOf {
+                function: function.to_string(),
+                expected: format!("{} or {last}", others.join(", ")),
+                actual: arguments[index].get_type(),
+                src: call_site.src.clone(),
+                call_location: call_site.location,
+                argument_location: call_site.argument_location(index),
+            }
+        }
+    }
+}
+
+fn native_argument(
+    function: &str,
+    expected: Type,
+    arguments: &[Value],
+    index: usize,
+    call_site: &CallSite,
+) -> RuntimeError {
+    NativeArgument {
+        function: function.to_string(),
+        expected,
+        actual: arguments[index].get_type(),
+        src: call_site.src.clone(),
+        call_location: call_site.location,
+        argument_location: call_site.argument_location(index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use miette::NamedSource;
+
+    use crate::{
+        ast::{
+            expr::Expr,
+            token::{Token, TokenType},
+        },
+        interpreter::{
+            runtime_error::RuntimeError::{self, *},
+            types::Type,
+            value::Value,
+        },
+    };
+
+    use super::{native_argument_one_of, CallSite};
+
+    #[test]
+    fn one_of_lists_every_expected_type() {
+        let err = one_of(&[Type::String, Type::List, Type::Map]);
+        assert_matches!(err, NativeArgumentOneOf { expected, .. } if expected == "String, List or Map");
+    }
+
+    #[test]
+    fn one_of_a_single_type_is_a_plain_argument_error() {
+        let err = one_of(&[Type::Number]);
+        assert_matches!(
+            err,
+            NativeArgument {
+                expected: Type::Number,
+                actual: Type::Boolean,
+                ..
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one type")]
+    fn one_of_no_types_is_a_bug() {
+        one_of(&[]);
+    }
+
+    fn one_of(expected: &[Type]) -> RuntimeError {
+        let src = Arc::new(NamedSource::new("native", String::new()));
+        let token = Token::new(TokenType::True, (0, 1).into(), src.clone());
+        let arguments = [Expr::literal(true.into(), &token)];
+        let call_site = CallSite {
+            src: &src,
+            location: (0, 1).into(),
+            arguments: &arguments,
+        };
+        native_argument_one_of("test", expected, &[Value::Boolean(true)], 0, &call_site)
+    }
+}
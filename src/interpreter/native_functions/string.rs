@@ -0,0 +1,74 @@
+use super::{
+    expect_integer, expect_string, CallSite, Interpreter, List, Result, RuntimeError::*, Value,
+};
+
+// start and length are counted in characters, not bytes
+pub(super) fn substr(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let string = expect_string("substr", &arguments, 0, call_site)?;
+    let start = expect_integer("substr", &arguments, 1, call_site)?;
+    let length = expect_integer("substr", &arguments, 2, call_site)?;
+    let char_count = string.chars().count();
+    let out_of_bounds = |index: f64, argument: usize| IndexOutOfBounds {
+        index,
+        length: char_count,
+        src: call_site.src.clone(),
+        location: call_site.argument_location(argument),
+    };
+    if start < 0 || start as usize > char_count {
+        return Err(out_of_bounds(start as f64, 1));
+    }
+    // compared against the rest of the string, start + length could overflow
+    if length < 0 || length as usize > char_count - start as usize {
+        return Err(out_of_bounds(start as f64 + length as f64, 2));
+    }
+    let substring = string
+        .chars()
+        .skip(start as usize)
+        .take(length as usize)
+        .collect::<String>();
+    Ok(Value::String(substring))
+}
+
+pub(super) fn upper(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let string = expect_string("upper", &arguments, 0, call_site)?;
+    Ok(Value::String(string.to_uppercase()))
+}
+
+pub(super) fn lower(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let string = expect_string("lower", &arguments, 0, call_site)?;
+    Ok(Value::String(string.to_lowercase()))
+}
+
+// an empty separator splits the string into its characters
+pub(super) fn split(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    call_site: &CallSite,
+) -> Result<Value> {
+    let string = expect_string("split", &arguments, 0, call_site)?;
+    let separator = expect_string("split", &arguments, 1, call_site)?;
+    let parts = if separator.is_empty() {
+        string
+            .chars()
+            .map(|c| Value::String(c.to_string()))
+            .collect()
+    } else {
+        string
+            .split(separator)
+            .map(|part| Value::String(part.to_string()))
+            .collect()
+    };
+    Ok(Value::List(List::new(parts)))
+}
@@ -1,5 +1,8 @@
+use std::{cell::RefCell, io::Write, ops::Add, rc::Rc};
+
 use super::value::Value;
 
+/// Receives every value printed by a Lox `print` statement.
 pub trait Printer {
     fn print(&self, value: Value);
 }
@@ -11,41 +14,61 @@ impl Printer for ConsolePrinter {
     }
 }
 
-#[cfg(test)]
-pub mod vec_printer {
-    use std::{cell::RefCell, ops::Add, rc::Rc};
+/// Streams printed values line by line into any [`Write`], panics like `println!` if writing fails.
+pub struct WriterPrinter<W: Write> {
+    writer: RefCell<W>,
+}
 
-    use crate::interpreter::value::Value;
+impl<W: Write> WriterPrinter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: RefCell::new(writer),
+        }
+    }
 
-    use super::Printer;
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+}
 
-    #[derive(Clone)]
-    pub struct VecPrinter {
-        lines: Rc<RefCell<Vec<Value>>>,
+impl<W: Write> Printer for WriterPrinter<W> {
+    fn print(&self, value: Value) {
+        writeln!(self.writer.borrow_mut(), "{}", value).expect("failed to write printed value")
     }
+}
 
-    impl VecPrinter {
-        pub fn new() -> Self {
-            Self {
-                lines: Rc::new(vec![].into()),
-            }
-        }
-        pub fn get_lines(&self) -> Vec<Value> {
-            self.lines.borrow().clone()
-        }
+/// Collects printed values in memory. Clones share the same lines, so a host can keep one
+/// clone and hand the other to [`Lox`](crate::Lox).
+#[derive(Clone, Default)]
+pub struct VecPrinter {
+    lines: Rc<RefCell<Vec<Value>>>,
+}
 
-        pub fn get_output(&self) -> String {
-            self.lines
-                .borrow()
-                .iter()
-                .map(|x| x.to_string().add("\n"))
-                .collect()
-        }
+impl VecPrinter {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    impl Printer for VecPrinter {
-        fn print(&self, value: Value) {
-            self.lines.borrow_mut().push(value)
-        }
+    pub fn get_lines(&self) -> Vec<Value> {
+        self.lines.borrow().clone()
+    }
+
+    /// Returns the lines printed so far and starts collecting from scratch.
+    pub fn take_lines(&self) -> Vec<Value> {
+        self.lines.take()
+    }
+
+    pub fn get_output(&self) -> String {
+        self.lines
+            .borrow()
+            .iter()
+            .map(|x| x.to_string().add("\n"))
+            .collect()
+    }
+}
+
+impl Printer for VecPrinter {
+    fn print(&self, value: Value) {
+        self.lines.borrow_mut().push(value)
     }
 }
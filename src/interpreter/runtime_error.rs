@@ -1,10 +1,13 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use miette::{Diagnostic, NamedSource, SourceSpan};
 
 use crate::{ast::name::Name, interpreter::types::Type};
 
-use super::value::Value;
+use super::{
+    call_stack::{self, CalledFrom, Frame},
+    value::Value,
+};
 
 #[derive(thiserror::Error, Debug, Diagnostic)]
 pub enum RuntimeError {
@@ -100,6 +103,157 @@ pub enum RuntimeError {
         location: SourceSpan,
     },
 
+    #[error("Can only index lists and maps but got {actual}")]
+    NotIndexable {
+        actual: Type,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("{actual}")]
+        location: SourceSpan,
+    },
+
+    #[error("Index must be an integer but got {actual}")]
+    NonIntegerIndex {
+        actual: String,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("not an integer")]
+        location: SourceSpan,
+    },
+
+    #[error("Index {index} out of bounds for length {length}")]
+    IndexOutOfBounds {
+        index: f64,
+        length: usize,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("out of bounds")]
+        location: SourceSpan,
+    },
+
+    #[error("Map keys must be String, Number, Boolean or Nil but got {actual}")]
+    UnhashableKey {
+        actual: Type,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("{actual} can't be used as a key")]
+        location: SourceSpan,
+    },
+
+    #[error("Undefined key {key}")]
+    UndefinedKey {
+        key: String,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Wrong argument type for \"{function}\": expected {expected} but got {actual}")]
+    #[diagnostic(help("Change argument to {expected}"))]
+    NativeArgument {
+        function: String,
+        expected: Type,
+        actual: Type,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("call")]
+        call_location: SourceSpan,
+        #[label("{actual}")]
+        argument_location: SourceSpan,
+    },
+
+    #[error("Wrong argument type for \"{function}\": expected {expected} but got {actual}")]
+    #[diagnostic(help("Change argument to {expected}"))]
+    NativeArgumentOneOf {
+        function: String,
+        /// The accepted types, as in "String, List or Map".
+        expected: String,
+        actual: Type,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("call")]
+        call_location: SourceSpan,
+        #[label("{actual}")]
+        argument_location: SourceSpan,
+    },
+
+    #[error("Argument {position} of \"{function}\" must be an integer but got {actual}")]
+    NonIntegerArgument {
+        function: String,
+        /// Counted from 1, as a reader would count the arguments.
+        position: usize,
+        actual: String,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("call")]
+        call_location: SourceSpan,
+        #[label("not an integer")]
+        argument_location: SourceSpan,
+    },
+
+    #[error("Can't convert \"{input}\" to a number")]
+    NotANumber {
+        input: String,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Unable to read input: {message}")]
+    InputError {
+        message: String,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("{message}")]
+    NativeFailure {
+        message: String,
+        #[source_code]
+        src: Option<Arc<NamedSource<String>>>,
+        #[label("in this call")]
+        location: Option<SourceSpan>,
+    },
+
+    #[error("Stack overflow: exceeded the maximum call depth of {limit}")]
+    StackOverflow {
+        limit: usize,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Exceeded the limit of {limit} executed statements")]
+    StepLimitExceeded {
+        limit: u64,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Execution timed out after {timeout:?}")]
+    Timeout {
+        timeout: Duration,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("{error}")]
+    #[diagnostic(forward(error))]
+    Traced {
+        error: Box<RuntimeError>,
+        #[related]
+        trace: Vec<CalledFrom>,
+    },
+
     #[error("Superclass was not a class but {actual}")]
     InvalidSuperclass {
         actual: Type,
@@ -110,10 +264,50 @@ pub enum RuntimeError {
     },
 }
 
+impl RuntimeError {
+    /// The error without the calls it happened in.
+    pub fn error(&self) -> &RuntimeError {
+        match self {
+            RuntimeError::Traced { error, .. } => error,
+            error => error,
+        }
+    }
+
+    /// The calls the error happened in, innermost first. Empty for errors in top-level code.
+    pub fn trace(&self) -> &[CalledFrom] {
+        match self {
+            RuntimeError::Traced { trace, .. } => trace,
+            _ => &[],
+        }
+    }
+
+    pub(super) fn with_trace(self, call_stack: &[Frame]) -> Self {
+        match self {
+            RuntimeError::Traced { .. } => self,
+            error => RuntimeError::Traced {
+                error: Box::new(error),
+                trace: call_stack::trace(call_stack),
+            },
+        }
+    }
+
+    /// Error for native functions registered by a host, the call site is filled in by the
+    /// interpreter.
+    pub fn native_failure(message: impl Into<String>) -> Self {
+        RuntimeError::NativeFailure {
+            message: message.into(),
+            src: None,
+            location: None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(super) enum RuntimeErrorOrReturn {
     RuntimeError(RuntimeError),
     Return(Value),
+    Break,
+    Continue,
 }
 
 impl From<RuntimeError> for RuntimeErrorOrReturn {
@@ -129,6 +323,9 @@ impl RuntimeErrorOrReturn {
             RuntimeErrorOrReturn::Return(_) => {
                 panic!("Return outside of function; should be guaranteed by static analysis")
             }
+            RuntimeErrorOrReturn::Break | RuntimeErrorOrReturn::Continue => {
+                panic!("Break or continue outside of loop; should be guaranteed by static analysis")
+            }
         }
     }
 }
@@ -19,15 +19,15 @@ use super::{
 impl Interpreter {
     pub(super) fn interpret_stmt(&mut self, statement: &Stmt) -> OrReturnResult<()> {
         use StmtType::*;
+        self.check_limits(statement)?;
         match &statement.stmt_type {
             Expression(expr) => self.interpret_expr(expr).map(|_| ())?,
             Print(expr) => self
                 .interpret_expr(expr)
                 .map(|value| self.printer.print(value))?,
             Var {
-                name: key,
-                initializer,
-            } => self.define_var(key, initializer)?,
+                name, initializer, ..
+            } => self.define_var(name, initializer)?,
             Block(stmts) => {
                 let local_env = Environment::from_parent(self.environment.clone());
                 self.execute_block(stmts, local_env)?
@@ -37,7 +37,13 @@ impl Interpreter {
                 then_stmt,
                 else_stmt,
             } => self.execute_if(condition, then_stmt, else_stmt.as_deref())?,
-            While { condition, body } => self.execute_while(condition, body.as_ref())?,
+            While {
+                condition,
+                body,
+                increment,
+            } => self.execute_while(condition, body.as_ref(), increment.as_ref())?,
+            Break => Err(RuntimeErrorOrReturn::Break)?,
+            Continue => Err(RuntimeErrorOrReturn::Continue)?,
             Function(function) => {
                 self.define_function(&function.name, &function.parameters, &function.body)?
             }
@@ -51,31 +57,34 @@ impl Interpreter {
         Ok(())
     }
 
-    fn define_var(&mut self, key: &Name, initializer: &Option<Expr>) -> Result<()> {
+    fn define_var(&mut self, name: &NameExpr, initializer: &Option<Expr>) -> Result<()> {
         let initializer = initializer
             .as_ref()
             .map_or(Ok(Value::Nil), |expr| self.interpret_expr(expr))?;
-        self.environment.borrow_mut().define(key, initializer);
+        self.define(name, initializer);
         Ok(())
     }
 
-    fn define_function(&mut self, name: &Name, parameters: &[Name], body: &[Stmt]) -> Result<()> {
+    fn define_function(
+        &mut self,
+        name: &NameExpr,
+        parameters: &[NameExpr],
+        body: &[Stmt],
+    ) -> Result<()> {
         let function = Function::new(
-            name.clone(),
-            parameters.to_vec(),
+            name.name.clone(),
+            parameter_names(parameters),
             body.to_vec(),
             self.environment.clone(),
             false,
         );
-        self.environment
-            .borrow_mut()
-            .define(name, Value::Callable(Callable::Function(function)));
+        self.define(name, Value::Callable(Callable::Function(function)));
         Ok(())
     }
 
     fn define_class(
         &mut self,
-        name: &Name,
+        name: &NameExpr,
         methods: &[stmt::Function],
         superclass: &Option<NameExpr>,
     ) -> Result<()> {
@@ -96,14 +105,11 @@ impl Interpreter {
             })
             .transpose()?;
 
-        self.environment.borrow_mut().define(name, Value::Nil);
+        self.define(name, Value::Nil);
 
         if let Some(superclass) = &superclass {
             let mut local_env = Environment::from_parent(self.environment.clone());
-            local_env.define(
-                &Name::super_name(),
-                Value::Callable(Callable::Class(superclass.clone())),
-            );
+            local_env.define(0, Value::Callable(Callable::Class(superclass.clone())));
             self.environment = Rc::new(RefCell::new(local_env))
         }
 
@@ -111,18 +117,18 @@ impl Interpreter {
             .iter()
             .map(|m| {
                 (
-                    m.name.clone(),
+                    m.name.name.clone(),
                     Function::new(
-                        m.name.clone(),
-                        m.parameters.clone(),
+                        m.name.name.clone(),
+                        parameter_names(&m.parameters),
                         m.body.clone(),
                         self.environment.clone(),
-                        m.name == Name::init(),
+                        m.name.name == Name::init(),
                     ),
                 )
             })
             .collect();
-        let class = Callable::Class(Class::new(name.clone(), superclass.clone(), methods));
+        let class = Callable::Class(Class::new(name.name.clone(), superclass.clone(), methods));
 
         if superclass.is_some() {
             let parent = self
@@ -135,9 +141,7 @@ impl Interpreter {
             self.environment = parent;
         }
 
-        self.environment
-            .borrow_mut()
-            .assign(name, &Value::Callable(class));
+        self.define(name, Value::Callable(class));
         Ok(())
     }
 
@@ -167,9 +171,21 @@ impl Interpreter {
         Ok(())
     }
 
-    fn execute_while(&mut self, condition: &Expr, body: &Stmt) -> OrReturnResult<()> {
+    fn execute_while(
+        &mut self,
+        condition: &Expr,
+        body: &Stmt,
+        increment: Option<&Expr>,
+    ) -> OrReturnResult<()> {
         while self.interpret_expr(condition)?.is_truthy() {
-            self.interpret_stmt(body)?;
+            match self.interpret_stmt(body) {
+                Ok(()) | Err(RuntimeErrorOrReturn::Continue) => (),
+                Err(RuntimeErrorOrReturn::Break) => break,
+                Err(err) => Err(err)?,
+            }
+            if let Some(increment) = increment {
+                self.interpret_expr(increment)?;
+            }
         }
         Ok(())
     }
@@ -180,6 +196,10 @@ impl Interpreter {
         Err(RuntimeErrorOrReturn::Return(value))
     }
 }
+fn parameter_names(parameters: &[NameExpr]) -> Vec<Name> {
+    parameters.iter().map(|p| p.name.clone()).collect()
+}
+
 #[cfg(test)]
 mod stmt_interpreter_tests {
 
@@ -190,11 +210,13 @@ mod stmt_interpreter_tests {
     use crate::{
         ast::{
             expr::Expr,
+            expr::ExprType,
             literal::Literal,
+            name::{NameExpr, Slot},
             stmt::{Stmt, StmtType},
             token::{Token, TokenType},
         },
-        interpreter::{printer::vec_printer::VecPrinter, runtime_error::RuntimeError, Interpreter},
+        interpreter::{printer::VecPrinter, runtime_error::RuntimeError, Interpreter},
     };
 
     #[test]
@@ -215,10 +237,7 @@ mod stmt_interpreter_tests {
         let stmt = block(vec![var("a")]);
         let mut interpreter = Interpreter::from_printer(Box::new(printer.clone()));
         interpreter.interpret_stmt(&stmt).unwrap();
-        let stmt = Stmt::expr(
-            Expr::variable("a".to_string(), token(TokenType::Eof)),
-            (0, 1).into(),
-        );
+        let stmt = Stmt::expr(local("a"), (0, 1).into());
         let err = interpreter
             .interpret_stmt(&stmt)
             .unwrap_err()
@@ -236,10 +255,7 @@ mod stmt_interpreter_tests {
         let stmt = block(vec![var("a"), read_undefined_var]);
         let mut interpreter = Interpreter::from_printer(Box::new(printer.clone()));
         let _ = interpreter.interpret_stmt(&stmt).unwrap_err();
-        let stmt = Stmt::expr(
-            Expr::variable("a".to_string(), token(TokenType::Eof)),
-            (0, 1).into(),
-        );
+        let stmt = Stmt::expr(local("a"), (0, 1).into());
         let err = interpreter
             .interpret_stmt(&stmt)
             .unwrap_err()
@@ -267,10 +283,26 @@ mod stmt_interpreter_tests {
         Expr::literal(literal, &token(TokenType::Eof))
     }
 
+    /// Reads the first slot of the innermost scope, where [`var`] declares.
+    fn local(name: &str) -> Expr {
+        let mut expr = Expr::variable(name.to_string(), token(TokenType::Eof));
+        if let ExprType::Variable(name) = &mut expr.expr_type {
+            name.slot = Some(Slot::new(0, 0));
+        }
+        expr
+    }
+
     fn var(name: &str) -> Stmt {
+        let mut name = NameExpr::new(
+            name.into(),
+            (0, 1).into(),
+            Arc::new(NamedSource::new("name", String::new())),
+        );
+        name.slot = Some(Slot::new(0, 0));
         Stmt {
             stmt_type: StmtType::Var {
-                name: name.into(),
+                name,
+                annotation: None,
                 initializer: None,
             },
             location: (0, 1).into(),
@@ -1,11 +1,15 @@
-#[derive(Debug, strum::Display)]
+/// The types of runtime values, also the types of annotations like `var x: Number`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
 pub enum Type {
     Function,
     NativeFunction,
     Class,
     Instance,
+    List,
+    Map,
     String,
     Number,
+    #[strum(to_string = "Boolean", serialize = "Bool")]
     Boolean,
     Nil,
 }
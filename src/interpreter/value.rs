@@ -1,11 +1,15 @@
-use std::fmt::Display;
+use std::{collections::HashSet, fmt::Display};
 
-use super::{callable::Callable, class::Instance, function::Function, types::Type};
+use super::{
+    callable::Callable, class::Instance, function::Function, list::List, map::Map, types::Type,
+};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
     Callable(Callable),
     Instance(Instance),
+    List(List),
+    Map(Map),
     String(String),
     Number(f64),
     Boolean(bool),
@@ -27,19 +31,41 @@ impl Value {
             Value::Callable(Callable::Native(_)) => Type::NativeFunction,
             Value::Callable(Callable::Class(_)) => Type::Class,
             Value::Instance(_) => Type::Instance,
+            Value::List(_) => Type::List,
+            Value::Map(_) => Type::Map,
             Value::String(_) => Type::String,
             Value::Number(_) => Type::Number,
             Value::Boolean(_) => Type::Boolean,
             Value::Nil => Type::Nil,
         }
     }
+
+    /// Prints lists and maps nested in this value, `printing` holds those already being
+    /// printed.
+    pub(super) fn fmt_nested(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        printing: &mut Printing,
+    ) -> std::fmt::Result {
+        match self {
+            Value::List(list) => list.fmt_nested(f, printing),
+            Value::Map(map) => map.fmt_nested(f, printing),
+            value => write!(f, "{}", value),
+        }
+    }
 }
 
+/// Addresses of the lists and maps being printed, one nested in itself is printed as `[...]`
+/// or `{...}`.
+pub(super) type Printing = HashSet<*const ()>;
+
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Value::Callable(c) => write!(f, "{}", c),
             Value::Instance(instance) => write!(f, "{}", instance),
+            Value::List(list) => write!(f, "{}", list),
+            Value::Map(map) => write!(f, "{}", map),
             Value::String(s) => write!(f, "{}", s),
             Value::Number(n) => write!(f, "{}", n),
             Value::Boolean(b) => write!(f, "{}", b),
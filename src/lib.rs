@@ -0,0 +1,54 @@
+//! A tree-walk interpreter for the Lox language.
+//!
+//! [`Lox`] runs a whole program through the [`Scanner`], [`Parser`], [`Resolver`],
+//! [`TypeChecker`] and [`Interpreter`]; the stages are exported as well for tools that only need part of the
+//! pipeline.
+pub mod ast;
+mod formatter;
+mod graphviz_converter;
+mod interpreter;
+mod linter;
+mod lox;
+mod optimizer;
+mod parser;
+mod resolver;
+mod scanner;
+mod source_span_extensions;
+mod type_checker;
+
+pub use formatter::Formatter;
+pub use interpreter::{
+    call_stack::CalledFrom,
+    limits::Limits,
+    printer::{ConsolePrinter, Printer, VecPrinter, WriterPrinter},
+    runtime_error::RuntimeError,
+    types::Type,
+    value::Value,
+    Interpreter,
+};
+pub use linter::{
+    lint_warning::{Lint, LintWarning},
+    Linter,
+};
+pub use lox::{Lox, LoxBuilder};
+pub use optimizer::Optimizer;
+pub use parser::{
+    parser_error::{ParserError, ParserErrors},
+    Parser,
+};
+pub use resolver::{
+    resolution_error::{ResolutionError, ResolutionErrors},
+    Resolver,
+};
+pub use scanner::{
+    scanner_error::{ScannerError, ScannerErrors},
+    Scanner,
+};
+pub use type_checker::{
+    type_error::{TypeError, TypeErrors},
+    TypeChecker,
+};
+
+#[cfg(test)]
+#[macro_use]
+extern crate assert_matches;
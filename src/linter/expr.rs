@@ -0,0 +1,81 @@
+use super::{lint_warning::LintWarning, Linter};
+use crate::ast::{
+    expr::{Expr, ExprType::*},
+    token::TokenType,
+};
+
+impl Linter {
+    pub(super) fn lint_expr(&mut self, expression: &Expr) {
+        match &expression.expr_type {
+            Assign(name_expr, expr) => {
+                self.lint_expr(expr);
+                if !self.is_declared(&name_expr.name) {
+                    self.warnings.push(LintWarning::UndeclaredAssignment {
+                        name: name_expr.name.clone(),
+                        src: name_expr.src.clone(),
+                        location: name_expr.location,
+                    })
+                }
+            }
+            Binary(lhs, operator, rhs) => {
+                if is_comparison(&operator.token_type) && same_operand(lhs, rhs) {
+                    self.warnings.push(LintWarning::SelfComparison {
+                        src: expression.src.clone(),
+                        location: expression.location,
+                    })
+                }
+                self.lint_expr(lhs);
+                self.lint_expr(rhs)
+            }
+            Logical(lhs, _, rhs) | Index(lhs, rhs) => {
+                self.lint_expr(lhs);
+                self.lint_expr(rhs)
+            }
+            Grouping(expr) | Unary(_, expr) | Get(expr, _) => self.lint_expr(expr),
+            Literal(_) | This(_) | Super(..) => (),
+            Variable(name_expr) => self.mark_used(&name_expr.name),
+            Call(callee, arguments) => {
+                self.lint_expr(callee);
+                arguments.iter().for_each(|e| self.lint_expr(e))
+            }
+            Set(object, _, value) => {
+                self.lint_expr(object);
+                self.lint_expr(value)
+            }
+            List(elements) | Interpolation(elements) => {
+                elements.iter().for_each(|e| self.lint_expr(e))
+            }
+            Map(entries) => entries.iter().for_each(|(key, value)| {
+                self.lint_expr(key);
+                self.lint_expr(value)
+            }),
+            SetIndex(object, index, value) => {
+                self.lint_expr(object);
+                self.lint_expr(index);
+                self.lint_expr(value)
+            }
+        }
+    }
+}
+
+fn is_comparison(operator: &TokenType) -> bool {
+    use TokenType::*;
+    matches!(
+        operator,
+        EqualEqual | BangEqual | Less | LessEqual | Greater | GreaterEqual
+    )
+}
+
+/// Both sides read the same variable or property, ignoring where they are written.
+fn same_operand(lhs: &Expr, rhs: &Expr) -> bool {
+    match (&lhs.expr_type, &rhs.expr_type) {
+        (Variable(lhs), Variable(rhs)) => lhs.name == rhs.name,
+        (This(_), This(_)) => true,
+        (Get(lhs_object, lhs), Get(rhs_object, rhs)) => {
+            lhs.name == rhs.name && same_operand(lhs_object, rhs_object)
+        }
+        (Grouping(lhs), _) => same_operand(lhs, rhs),
+        (_, Grouping(rhs)) => same_operand(lhs, rhs),
+        _ => false,
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use strum::{Display, EnumIter, EnumString};
+
+use crate::ast::name::Name;
+
+/// The kinds of [`LintWarning`], each can be switched off on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Lint {
+    UnusedVariable,
+    UnusedParameter,
+    Shadowing,
+    UnreachableCode,
+    UndeclaredAssignment,
+    SelfComparison,
+}
+
+#[derive(thiserror::Error, Debug, Diagnostic)]
+pub enum LintWarning {
+    #[error("Unused local variable \"{name}\"")]
+    #[diagnostic(
+        severity(Warning),
+        help("Remove it or start its name with an underscore")
+    )]
+    UnusedVariable {
+        name: Name,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("declared here")]
+        location: SourceSpan,
+    },
+    #[error("Unused parameter \"{name}\"")]
+    #[diagnostic(
+        severity(Warning),
+        help("Remove it or start its name with an underscore")
+    )]
+    UnusedParameter {
+        name: Name,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("declared here")]
+        location: SourceSpan,
+    },
+    #[error("\"{name}\" shadows an outer declaration")]
+    #[diagnostic(severity(Warning))]
+    Shadowing {
+        name: Name,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("declared here")]
+        location: SourceSpan,
+        #[label("shadows this")]
+        shadowed: SourceSpan,
+    },
+    #[error("Unreachable code")]
+    #[diagnostic(severity(Warning))]
+    UnreachableCode {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("never executed")]
+        location: SourceSpan,
+        #[label("after this")]
+        exit: SourceSpan,
+    },
+    #[error("Assignment to undeclared variable \"{name}\"")]
+    #[diagnostic(
+        severity(Warning),
+        help("Declare it with var, assigning an undeclared variable fails at runtime")
+    )]
+    UndeclaredAssignment {
+        name: Name,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+    #[error("Comparison of an expression with itself")]
+    #[diagnostic(severity(Warning))]
+    SelfComparison {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("both sides are the same")]
+        location: SourceSpan,
+    },
+}
+
+impl LintWarning {
+    pub fn lint(&self) -> Lint {
+        use LintWarning::*;
+        match self {
+            UnusedVariable { .. } => Lint::UnusedVariable,
+            UnusedParameter { .. } => Lint::UnusedParameter,
+            Shadowing { .. } => Lint::Shadowing,
+            UnreachableCode { .. } => Lint::UnreachableCode,
+            UndeclaredAssignment { .. } => Lint::UndeclaredAssignment,
+            SelfComparison { .. } => Lint::SelfComparison,
+        }
+    }
+
+    /// Where the warning starts, to report warnings in source order.
+    pub(super) fn offset(&self) -> usize {
+        use LintWarning::*;
+        match self {
+            UnusedVariable { location, .. }
+            | UnusedParameter { location, .. }
+            | Shadowing { location, .. }
+            | UnreachableCode { location, .. }
+            | UndeclaredAssignment { location, .. }
+            | SelfComparison { location, .. } => location.offset(),
+        }
+    }
+}
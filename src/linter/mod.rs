@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+mod expr;
+pub mod lint_warning;
+mod statement;
+
+use crate::ast::{
+    name::{Name, NameExpr},
+    stmt::{Stmt, StmtType},
+};
+
+use self::lint_warning::LintWarning;
+
+/// Finds code that runs but is likely a mistake. Unlike [`crate::Resolver`] errors the
+/// warnings never stop a program.
+#[derive(Debug, Default)]
+pub struct Linter {
+    scopes: Vec<HashMap<Name, Local>>,
+    /// Top-level declarations of the program, and globals defined before it ran without one.
+    globals: HashMap<Name, Option<NameExpr>>,
+    warnings: Vec<LintWarning>,
+}
+
+#[derive(Debug)]
+struct Local {
+    declaration: NameExpr,
+    kind: LocalKind,
+    used: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum LocalKind {
+    Variable,
+    Parameter,
+    Function,
+    Class,
+}
+
+impl Linter {
+    /// `known_globals` are defined before the program runs, like natives or earlier REPL inputs.
+    pub fn lint(
+        statements: &[Stmt],
+        known_globals: impl IntoIterator<Item = Name>,
+    ) -> Vec<LintWarning> {
+        let mut linter = Linter::default();
+        known_globals.into_iter().for_each(|name| {
+            linter.globals.insert(name, None);
+        });
+        statements.iter().for_each(|statement| {
+            if let Some(name) = declared_name(statement) {
+                linter.globals.insert(name.name.clone(), Some(name.clone()));
+            }
+        });
+        linter.lint_statements(statements);
+        linter.warnings.sort_by_key(LintWarning::offset);
+        linter.warnings
+    }
+
+    /// Adds a local to the current scope, globals are collected up front.
+    fn declare(&mut self, name: &NameExpr, kind: LocalKind) {
+        if self.scopes.is_empty() {
+            return;
+        }
+        self.check_shadowing(name);
+        if let Some(scope) = self.scopes.last_mut() {
+            let local = Local {
+                declaration: name.clone(),
+                kind,
+                used: false,
+            };
+            scope.insert(name.name.clone(), local);
+        }
+    }
+
+    fn check_shadowing(&mut self, name: &NameExpr) {
+        let enclosing = self.scopes.len() - 1;
+        let shadowed = self.scopes[..enclosing]
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&name.name))
+            .map(|local| local.declaration.location)
+            .or_else(|| {
+                self.globals
+                    .get(&name.name)
+                    .and_then(|global| global.as_ref().map(|g| g.location))
+            });
+        if let Some(shadowed) = shadowed {
+            self.warnings.push(LintWarning::Shadowing {
+                name: name.name.clone(),
+                src: name.src.clone(),
+                location: name.location,
+                shadowed,
+            })
+        }
+    }
+
+    fn is_declared(&self, name: &Name) -> bool {
+        self.scopes.iter().any(|scope| scope.contains_key(name)) || self.globals.contains_key(name)
+    }
+
+    fn mark_used(&mut self, name: &Name) {
+        if let Some(local) = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+        {
+            local.used = true;
+        }
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        let Some(scope) = self.scopes.pop() else {
+            return;
+        };
+        let unused = scope
+            .into_values()
+            .filter(|local| !local.used && !local.declaration.name.to_string().starts_with('_'));
+        for local in unused {
+            let Local {
+                declaration:
+                    NameExpr {
+                        name,
+                        location,
+                        src,
+                        ..
+                    },
+                kind,
+                ..
+            } = local;
+            match kind {
+                LocalKind::Variable => self.warnings.push(LintWarning::UnusedVariable {
+                    name,
+                    src,
+                    location,
+                }),
+                LocalKind::Parameter => self.warnings.push(LintWarning::UnusedParameter {
+                    name,
+                    src,
+                    location,
+                }),
+                LocalKind::Function | LocalKind::Class => (),
+            }
+        }
+    }
+}
+
+fn declared_name(statement: &Stmt) -> Option<&NameExpr> {
+    match &statement.stmt_type {
+        StmtType::Var { name, .. } | StmtType::Class { name, .. } => Some(name),
+        StmtType::Function(function) => Some(&function.name),
+        _ => None,
+    }
+}
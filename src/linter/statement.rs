@@ -0,0 +1,88 @@
+use super::{lint_warning::LintWarning, Linter, LocalKind};
+use crate::{
+    ast::stmt::{Function, Stmt, StmtType::*},
+    source_span_extensions::SourceSpanExtensions,
+};
+
+impl Linter {
+    pub(super) fn lint_statements(&mut self, statements: &[Stmt]) {
+        statements.iter().for_each(|s| self.lint_statement(s));
+        self.check_unreachable(statements);
+    }
+
+    fn lint_statement(&mut self, statement: &Stmt) {
+        match &statement.stmt_type {
+            Expression(expr) | Print(expr) => self.lint_expr(expr),
+            Var {
+                name, initializer, ..
+            } => {
+                initializer.iter().for_each(|e| self.lint_expr(e));
+                self.declare(name, LocalKind::Variable);
+            }
+            Function(function) => {
+                self.declare(&function.name, LocalKind::Function);
+                self.lint_function(function);
+            }
+            Return(expr) => expr.iter().for_each(|e| self.lint_expr(e)),
+            Block(statements) => {
+                self.begin_scope();
+                self.lint_statements(statements);
+                self.end_scope();
+            }
+            If {
+                condition,
+                then_stmt,
+                else_stmt,
+            } => {
+                self.lint_expr(condition);
+                self.lint_statement(then_stmt);
+                else_stmt.iter().for_each(|s| self.lint_statement(s));
+            }
+            While {
+                condition,
+                body,
+                increment,
+            } => {
+                self.lint_expr(condition);
+                self.lint_statement(body);
+                increment.iter().for_each(|e| self.lint_expr(e));
+            }
+            Break | Continue => (),
+            Class {
+                name,
+                methods,
+                superclass,
+            } => {
+                self.declare(name, LocalKind::Class);
+                superclass.iter().for_each(|s| self.mark_used(&s.name));
+                methods.iter().for_each(|m| self.lint_function(m));
+            }
+        }
+    }
+
+    fn lint_function(&mut self, function: &Function) {
+        self.begin_scope();
+        function
+            .parameters
+            .iter()
+            .for_each(|p| self.declare(p, LocalKind::Parameter));
+        self.lint_statements(&function.body);
+        self.end_scope();
+    }
+
+    /// Statements following a `return`, `break` or `continue` in the same block.
+    fn check_unreachable(&mut self, statements: &[Stmt]) {
+        let exit = statements
+            .iter()
+            .position(|s| matches!(s.stmt_type, Return(_) | Break | Continue));
+        if let (Some(exit), Some(last)) = (exit, statements.last()) {
+            if let Some(first_unreachable) = statements.get(exit + 1) {
+                self.warnings.push(LintWarning::UnreachableCode {
+                    src: first_unreachable.src.clone(),
+                    location: first_unreachable.location.until(last.location),
+                    exit: statements[exit].location,
+                })
+            }
+        }
+    }
+}
@@ -1,36 +1,148 @@
+use std::collections::HashSet;
+
 use miette::NamedSource;
 
 use crate::{
+    ast::stmt::Stmt,
     graphviz_converter,
-    interpreter::{value::Value, Interpreter},
+    interpreter::{
+        self,
+        limits::Limits,
+        printer::{ConsolePrinter, Printer},
+        value::Value,
+        Interpreter,
+    },
+    linter::{
+        lint_warning::{Lint, LintWarning},
+        Linter,
+    },
+    optimizer::Optimizer,
     parser::{parser_error::ParserError::ExpectedSemicolon, Parser},
-    resolver::Resolver,
+    resolver::{resolution_error::ResolutionErrors, Resolver},
     scanner::Scanner,
+    type_checker::TypeChecker,
 };
 
+/// Receives the warnings of the lints enabled with [`LoxBuilder::lint`].
+type WarningSink = Box<dyn FnMut(LintWarning)>;
+
 pub struct Lox {
     interpreter: Interpreter,
+    lints: HashSet<Lint>,
+    warnings: Option<WarningSink>,
+    strict: bool,
     verbose: bool,
     graphviz: bool,
 }
 
+/// Configures a [`Lox`] before it runs anything, see [`Lox::builder`].
+pub struct LoxBuilder {
+    printer: Box<dyn Printer>,
+    limits: Limits,
+    lints: HashSet<Lint>,
+    warnings: Option<WarningSink>,
+    strict: bool,
+    verbose: bool,
+    graphviz: bool,
+}
+
+impl LoxBuilder {
+    /// Where `print` statements write to, the console by default.
+    pub fn printer(mut self, printer: Box<dyn Printer>) -> Self {
+        self.printer = printer;
+        self
+    }
+
+    /// Stops runaway programs with a runtime error, see [`Limits::default`].
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Reports one kind of warning to the [`LoxBuilder::warnings`] sink before running a
+    /// program, nothing is linted by default.
+    pub fn lint(mut self, lint: Lint) -> Self {
+        self.lints.insert(lint);
+        self
+    }
+
+    /// Where the warnings of the enabled lints go. They never stop the program.
+    pub fn warnings(mut self, sink: impl FnMut(LintWarning) + 'static) -> Self {
+        self.warnings = Some(Box::new(sink));
+        self
+    }
+
+    /// Rejects programs using variables that are not declared anywhere before running them.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Dumps tokens, statements, resolved locals and the optimized statements to stderr.
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Prints the syntax tree as graphviz instead of running the program.
+    pub fn graphviz(mut self, graphviz: bool) -> Self {
+        self.graphviz = graphviz;
+        self
+    }
+
+    pub fn build(self) -> Lox {
+        let mut interpreter = Interpreter::from_printer(self.printer);
+        interpreter.set_limits(self.limits);
+        Lox {
+            interpreter,
+            lints: self.lints,
+            warnings: self.warnings,
+            strict: self.strict,
+            verbose: self.verbose,
+            graphviz: self.graphviz,
+        }
+    }
+}
+
 impl Lox {
     pub fn new(verbose: bool, graphviz: bool) -> Self {
-        Self {
-            interpreter: Interpreter::new(),
-            verbose,
-            graphviz,
+        Self::builder().verbose(verbose).graphviz(graphviz).build()
+    }
+
+    pub fn builder() -> LoxBuilder {
+        LoxBuilder {
+            printer: Box::new(ConsolePrinter),
+            limits: Limits::default(),
+            lints: HashSet::new(),
+            warnings: None,
+            strict: false,
+            verbose: false,
+            graphviz: false,
         }
     }
 
+    /// Makes a host function callable from Lox under `name`. Errors created with
+    /// [`RuntimeError::native_failure`](crate::RuntimeError::native_failure) are reported at
+    /// the Lox call site.
+    pub fn register_native(
+        &mut self,
+        name: &str,
+        arity: usize,
+        function: impl Fn(&mut Interpreter, Vec<Value>) -> interpreter::Result<Value> + 'static,
+    ) {
+        self.interpreter.register_native(name, arity, function)
+    }
+
     pub fn run(&mut self, source: String, named_source: NamedSource<String>) -> miette::Result<()> {
         let tokens = Scanner::scan(source, named_source, self.verbose)?;
-        let statements = Parser::parse(tokens, self.verbose)?;
-        let locals = Resolver::resolve(&statements, self.verbose)?;
-        self.interpreter.add_locals(locals);
+        let mut statements = Parser::parse(tokens, self.verbose)?;
+        self.resolve(&mut statements)?;
+        TypeChecker::check(&statements)?;
+        self.report_warnings(&statements);
         if self.graphviz {
             graphviz_converter::print_graphviz(statements);
         } else {
+            let statements = Optimizer::optimize(statements, self.verbose);
             self.interpreter.interpret(&statements)?;
         }
         Ok(())
@@ -44,9 +156,11 @@ impl Lox {
         let named_source = NamedSource::new(format!("repl({repl_counter})"), source.clone());
         let tokens = Scanner::scan(source, named_source, self.verbose)?;
         match Parser::parse(tokens, self.verbose) {
-            Ok(statements) => {
-                let locals = Resolver::resolve(&statements, self.verbose)?;
-                self.interpreter.add_locals(locals);
+            Ok(mut statements) => {
+                self.resolve(&mut statements)?;
+                TypeChecker::check(&statements)?;
+                self.report_warnings(&statements);
+                let statements = Optimizer::optimize(statements, self.verbose);
                 self.interpreter.interpret(&statements)?;
                 Ok(None)
             }
@@ -60,46 +174,112 @@ impl Lox {
                         eprintln!("No statement found. Fallback to expression:");
                         eprintln!("{}", expr);
                     }
-                    let locals = Resolver::resolve_expression(expr, self.verbose)?;
-                    self.interpreter.add_locals(locals);
-                    let result = self.interpreter.interpret_expr(expr)?;
+                    let mut expr = *expr.clone();
+                    if self.strict {
+                        let globals = self.interpreter.global_names();
+                        Resolver::resolve_expression_strict(&mut expr, globals, self.verbose)?
+                    } else {
+                        Resolver::resolve_expression(&mut expr, self.verbose)?
+                    };
+                    TypeChecker::check_expression(&expr)?;
+                    let expr = Optimizer::optimize_expression(expr);
+                    let result = self.interpreter.evaluate(&expr)?;
                     Ok(Some(result))
                 }
                 _ => Err(parser_errors)?,
             },
         }
     }
+
+    /// In strict mode the globals defined so far count as declared, including those of
+    /// earlier REPL inputs.
+    fn resolve(&self, statements: &mut [Stmt]) -> Result<(), ResolutionErrors> {
+        if self.strict {
+            let globals = self.interpreter.global_names();
+            Resolver::resolve_strict(statements, globals, self.verbose)
+        } else {
+            Resolver::resolve(statements, self.verbose)
+        }
+    }
+
+    fn report_warnings(&mut self, statements: &[Stmt]) {
+        let Some(sink) = &mut self.warnings else {
+            return;
+        };
+        if self.lints.is_empty() {
+            return;
+        }
+        Linter::lint(statements, self.interpreter.global_names())
+            .into_iter()
+            .filter(|warning| self.lints.contains(&warning.lint()))
+            .for_each(sink);
+    }
 }
 
 #[cfg(test)]
 mod lox_tests {
+    use std::{
+        cell::{Cell, RefCell},
+        fs,
+        path::{Path, PathBuf},
+        rc::Rc,
+    };
+
     use crate::{
-        interpreter::printer::{vec_printer::VecPrinter, Printer},
-        interpreter::Interpreter,
+        formatter::Formatter, interpreter::printer::VecPrinter,
+        interpreter::runtime_error::RuntimeError, linter::Linter, parser::Parser, scanner::Scanner,
     };
     use datadriven::walk;
     use miette::NamedSource;
     use serde_json::Value;
 
-    use super::Lox;
-    impl Lox {
-        pub fn with_printer(printer: Box<dyn Printer>) -> Self {
-            Self {
-                interpreter: Interpreter::from_printer(printer),
-                verbose: false,
-                graphviz: false,
-            }
-        }
+    use super::{Lox, LoxBuilder};
+    use crate::linter::lint_warning::{Lint, LintWarning};
+
+    fn with_printer(printer: VecPrinter) -> Lox {
+        Lox::builder().printer(Box::new(printer)).build()
     }
+
     #[test]
     fn integration_tests() {
-        walk("tests/", |f| {
+        lox_files(Path::new("tests/"))
+            .iter()
+            .for_each(|path| run_lox_file(path));
+    }
+
+    // tests/ also holds the Rust integration tests, datadriven would try to parse those too
+    fn lox_files(dir: &Path) -> Vec<PathBuf> {
+        let mut files = vec![];
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files.extend(lox_files(&path));
+            } else if path.extension().is_some_and(|extension| extension == "lox") {
+                files.push(path);
+            }
+        }
+        files.sort();
+        files
+    }
+
+    fn run_lox_file(path: &Path) {
+        walk(path.to_str().unwrap(), |f| {
             let file_name = f.filename.clone();
             f.run(|test_case| -> String {
                 let input = test_case.input.to_string();
                 let printer = VecPrinter::new();
-                let mut lox = Lox::with_printer(Box::new(printer.clone()));
+                // `error strict` and `run strict` resolve in strict mode
+                let mut lox = Lox::builder()
+                    .printer(Box::new(printer.clone()))
+                    .strict(test_case.args.contains_key("strict"))
+                    .build();
                 let named_source = NamedSource::new(file_name.clone(), input.clone());
+                if test_case.directive == "fmt" {
+                    return format_idempotent(input, &file_name);
+                }
+                if test_case.directive == "lint" {
+                    return lint_warnings(&lox, input, named_source);
+                }
                 let result = lox.run(input, named_source);
                 if test_case.directive == "error" {
                     let err = result.expect_err(
@@ -119,6 +299,94 @@ mod lox_tests {
         });
     }
 
+    fn lint_warnings(lox: &Lox, input: String, named_source: NamedSource<String>) -> String {
+        let tokens = Scanner::scan(input, named_source, false).unwrap();
+        let statements = Parser::parse(tokens, false).unwrap();
+        Linter::lint(&statements, lox.interpreter.global_names())
+            .into_iter()
+            .map(|warning| {
+                let mut json = String::new();
+                miette::JSONReportHandler::new()
+                    .render_report(&mut json, &warning)
+                    .unwrap();
+                format_json(json) + "\n"
+            })
+            .collect()
+    }
+
+    fn format_idempotent(input: String, file_name: &str) -> String {
+        let format = |source: &String| {
+            let named_source = NamedSource::new(file_name, source.clone());
+            Formatter::format(source.clone(), named_source)
+                .unwrap_or_else(|err| panic!("Test {file_name} failed to format: {err:?}"))
+        };
+        let formatted = format(&input);
+        assert_eq!(
+            format(&formatted),
+            formatted,
+            "Formatting {file_name} twice changed it"
+        );
+        formatted
+    }
+
+    #[test]
+    fn register_capturing_native() {
+        let printer = VecPrinter::new();
+        let mut lox = with_printer(printer.clone());
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        lox.register_native("next_id", 1, move |_, arguments| {
+            counter.set(counter.get() + 1);
+            Ok(format!("{}-{}", arguments[0], counter.get()).into())
+        });
+        let source = "print next_id(\"a\"); print next_id(\"b\");".to_string();
+        let named_source = NamedSource::new("host", source.clone());
+        lox.run(source, named_source).unwrap();
+        assert_eq!(printer.get_output(), "a-1\nb-2\n");
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn reports_enabled_lints_to_sink() {
+        let source = "fun f(a) { var b; print a == a; }".to_string();
+        let run = |lox: LoxBuilder| {
+            let warnings = Rc::new(RefCell::new(vec![]));
+            let sink = warnings.clone();
+            let mut lox = lox
+                .printer(Box::new(VecPrinter::new()))
+                .warnings(move |warning: LintWarning| sink.borrow_mut().push(warning.lint()))
+                .build();
+            let named_source = NamedSource::new("lint", source.clone());
+            lox.run(source.clone(), named_source).unwrap();
+            warnings.take()
+        };
+        assert_eq!(run(Lox::builder()), vec![]);
+        let lox = Lox::builder()
+            .lint(Lint::UnusedVariable)
+            .lint(Lint::SelfComparison);
+        assert_eq!(run(lox), vec![Lint::UnusedVariable, Lint::SelfComparison]);
+    }
+
+    #[test]
+    fn native_failure_is_labelled_with_call_site() {
+        let mut lox = with_printer(VecPrinter::new());
+        lox.register_native("fail", 0, |_, _| {
+            Err(RuntimeError::native_failure("host unavailable"))
+        });
+        let source = "var a = 1;\nfail();".to_string();
+        let named_source = NamedSource::new("host", source.clone());
+        let err = lox.run(source, named_source).unwrap_err();
+        let err = err.downcast_ref::<RuntimeError>().unwrap();
+        assert_matches!(
+            err.error(),
+            RuntimeError::NativeFailure {
+                message,
+                src: Some(_),
+                location: Some(location),
+            } if message == "host unavailable" && *location == (11, 6).into()
+        );
+    }
+
     fn format_json(json: String) -> String {
         let x: Value = serde_json::from_str(json.as_str()).unwrap();
         serde_json::to_string_pretty(&x).unwrap()
@@ -1,17 +1,9 @@
 mod args;
-mod ast;
-mod graphviz_converter;
-mod interpreter;
-mod lox;
-mod parser;
-mod resolver;
-mod scanner;
-mod source_span_extensions;
 
 use std::fs;
 
 use clap::Parser;
-use lox::Lox;
+use rust_lox::{Formatter, Lint, Lox};
 
 use args::Args;
 use miette::{IntoDiagnostic, NamedSource};
@@ -19,6 +11,7 @@ use rustyline::{
     error::ReadlineError, highlight::MatchingBracketHighlighter,
     validate::MatchingBracketValidator, Completer, Editor, Helper, Highlighter, Hinter, Validator,
 };
+use strum::IntoEnumIterator;
 
 fn main() {
     let args = Args::parse();
@@ -26,7 +19,29 @@ fn main() {
         eprintln!("graphviz works only with input file");
         std::process::exit(5)
     }
-    let lox = Lox::new(args.verbose, args.graphviz);
+    if args.fmt {
+        let Some(file) = args.file else {
+            eprintln!("fmt works only with input file");
+            std::process::exit(5)
+        };
+        match format_file(file, args.check) {
+            Ok(true) => (),
+            Ok(false) => std::process::exit(1),
+            Err(err) => {
+                eprintln!("{:?}", err);
+                std::process::exit(65)
+            }
+        }
+        return;
+    }
+    let lox = Lint::iter()
+        .filter(|lint| !args.allow.contains(lint))
+        .fold(Lox::builder(), |builder, lint| builder.lint(lint))
+        .warnings(|warning| eprintln!("{:?}", miette::Report::new(warning)))
+        .strict(args.strict)
+        .verbose(args.verbose)
+        .graphviz(args.graphviz)
+        .build();
     let result = match args.file {
         Some(file) => run_file(lox, file),
         None => run_prompt(lox, args).into_diagnostic(),
@@ -47,6 +62,22 @@ fn run_file(mut lox: Lox, file: String) -> miette::Result<()> {
     lox.run(contents, named_source)
 }
 
+/// Returns false if `check` is set and the file is not formatted.
+fn format_file(file: String, check: bool) -> miette::Result<bool> {
+    let contents = fs::read_to_string(file.clone()).into_diagnostic()?;
+    let named_source = NamedSource::new(file.clone(), contents.clone());
+    let formatted = Formatter::format(contents.clone(), named_source)?;
+    if formatted == contents {
+        Ok(true)
+    } else if check {
+        eprintln!("{file} is not formatted");
+        Ok(false)
+    } else {
+        fs::write(file, formatted).into_diagnostic()?;
+        Ok(true)
+    }
+}
+
 fn run_prompt(mut lox: Lox, args: Args) -> rustyline::Result<()> {
     #[derive(Helper, Completer, Hinter, Validator, Highlighter, Default)]
     struct MyHelper {
@@ -90,7 +121,3 @@ fn run_prompt(mut lox: Lox, args: Args) -> rustyline::Result<()> {
     }
     Ok(())
 }
-
-#[cfg(test)]
-#[macro_use]
-extern crate assert_matches;
@@ -0,0 +1,113 @@
+use crate::ast::{
+    expr::{Expr, ExprType},
+    literal::Literal,
+    token::TokenType,
+};
+
+pub(super) fn optimize_expr(expression: Expr) -> Expr {
+    use ExprType::*;
+    let Expr {
+        expr_type,
+        location,
+        src,
+    } = expression;
+    let optimize_box = |expr: Box<Expr>| Box::new(optimize_expr(*expr));
+    let expr_type = match expr_type {
+        Grouping(expr) => {
+            let expr = optimize_expr(*expr);
+            match expr.expr_type {
+                Literal(literal) => Literal(literal),
+                _ => Grouping(Box::new(expr)),
+            }
+        }
+        Unary(token, expr) => {
+            let expr = optimize_expr(*expr);
+            match fold_unary(&token.token_type, &expr) {
+                Some(literal) => Literal(literal),
+                None => Unary(token, Box::new(expr)),
+            }
+        }
+        Binary(left, token, right) => {
+            let left = optimize_expr(*left);
+            let right = optimize_expr(*right);
+            match fold_binary(&left, &token.token_type, &right) {
+                Some(literal) => Literal(literal),
+                None => Binary(Box::new(left), token, Box::new(right)),
+            }
+        }
+        Logical(left, token, right) => {
+            let left = optimize_expr(*left);
+            let right = optimize_expr(*right);
+            if let Literal(literal) = &left.expr_type {
+                // the value of a logical operator is one of its operands
+                let short_circuits = match token.token_type {
+                    TokenType::Or => literal.is_truthy(),
+                    _ => !literal.is_truthy(),
+                };
+                return if short_circuits { left } else { right };
+            }
+            Logical(Box::new(left), token, Box::new(right))
+        }
+        Assign(name, value) => Assign(name, optimize_box(value)),
+        Call(callee, arguments) => Call(
+            optimize_box(callee),
+            arguments.into_iter().map(optimize_expr).collect(),
+        ),
+        Get(object, name) => Get(optimize_box(object), name),
+        Set(object, name, value) => Set(optimize_box(object), name, optimize_box(value)),
+        List(elements) => List(elements.into_iter().map(optimize_expr).collect()),
+        Map(entries) => Map(entries
+            .into_iter()
+            .map(|(key, value)| (optimize_expr(key), optimize_expr(value)))
+            .collect()),
+        Index(object, index) => Index(optimize_box(object), optimize_box(index)),
+        SetIndex(object, index, value) => SetIndex(
+            optimize_box(object),
+            optimize_box(index),
+            optimize_box(value),
+        ),
+        Interpolation(parts) => Interpolation(parts.into_iter().map(optimize_expr).collect()),
+        expr_type @ (Literal(_) | Variable(_) | This(_) | Super(..)) => expr_type,
+    };
+    Expr {
+        expr_type,
+        location,
+        src,
+    }
+}
+
+fn literal(expr: &Expr) -> Option<&Literal> {
+    match &expr.expr_type {
+        ExprType::Literal(literal) => Some(literal),
+        _ => None,
+    }
+}
+
+/// Operations that would fail at runtime are not folded, so they still report their error.
+fn fold_unary(operator: &TokenType, expr: &Expr) -> Option<Literal> {
+    match (operator, literal(expr)?) {
+        (TokenType::Minus, Literal::Number(n)) => Some(Literal::Number(-n)),
+        (TokenType::Bang, literal) => Some(Literal::Boolean(!literal.is_truthy())),
+        _ => None,
+    }
+}
+
+fn fold_binary(left: &Expr, operator: &TokenType, right: &Expr) -> Option<Literal> {
+    use Literal::{Boolean, Number, String};
+    use TokenType::*;
+    let folded = match (literal(left)?, operator, literal(right)?) {
+        (Number(l), Plus, Number(r)) => Number(l + r),
+        (String(l), Plus, String(r)) => String(format!("{l}{r}")),
+        (Number(l), Minus, Number(r)) => Number(l - r),
+        (Number(l), Star, Number(r)) => Number(l * r),
+        (Number(l), Slash, Number(r)) => Number(l / r),
+        (Number(l), Greater, Number(r)) => Boolean(l > r),
+        (Number(l), GreaterEqual, Number(r)) => Boolean(l >= r),
+        (Number(l), Less, Number(r)) => Boolean(l < r),
+        (Number(l), LessEqual, Number(r)) => Boolean(l <= r),
+        (l, EqualEqual, r) => Boolean(l == r),
+        (l, BangEqual, r) => Boolean(l != r),
+        _ => return None,
+    };
+    Some(folded)
+}
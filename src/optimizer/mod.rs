@@ -0,0 +1,104 @@
+mod expr;
+mod statement;
+
+use crate::ast::{expr::Expr, stmt::Stmt};
+
+/// Folds operations on literals and removes branches that can never run. Folded expressions
+/// keep the span of the code they replace, so errors still point at the source.
+pub struct Optimizer;
+
+impl Optimizer {
+    pub fn optimize(statements: Vec<Stmt>, verbose: bool) -> Vec<Stmt> {
+        let statements = statement::optimize_statements(statements);
+        if verbose {
+            eprintln!("Optimized statements:");
+            statements.iter().for_each(|s| eprint!("{}", s));
+        }
+        statements
+    }
+
+    pub fn optimize_expression(expression: Expr) -> Expr {
+        expr::optimize_expr(expression)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use miette::NamedSource;
+
+    use crate::{parser::Parser, scanner::Scanner};
+
+    use super::Optimizer;
+
+    fn optimize(source: &str) -> String {
+        let named_source = NamedSource::new("", source.to_string());
+        let tokens = Scanner::scan(source.to_string(), named_source, false).unwrap();
+        let statements = Parser::parse(tokens, false).unwrap();
+        Optimizer::optimize(statements, false)
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn fold_arithmetic() {
+        assert_eq!(optimize("print 1 + 2 * (3 - -1);"), "Print(9)\n");
+        assert_eq!(optimize("print 1 < 2 == !nil;"), "Print(true)\n");
+    }
+
+    #[test]
+    fn fold_string_concatenation() {
+        assert_eq!(optimize("print \"a\" + \"b\" + \"c\";"), "Print(\"abc\")\n");
+    }
+
+    #[test]
+    fn fold_logical_operators() {
+        assert_eq!(optimize("print nil or a;"), "Print(variable a)\n");
+        assert_eq!(optimize("print 1 and false or 2;"), "Print(2)\n");
+        assert_eq!(
+            optimize("print a and 1 + 1;"),
+            "Print(Logical and (variable a) (2))\n"
+        );
+    }
+
+    #[test]
+    fn keep_operations_failing_at_runtime() {
+        assert_eq!(optimize("print 1 + \"a\";"), "Print(+ (1) (\"a\"))\n");
+        assert_eq!(optimize("print -nil;"), "Print(- (nil))\n");
+    }
+
+    #[test]
+    fn keep_span_of_folded_expression() {
+        let source = "print 1 + (2 + 3) - \"a\";";
+        let named_source = NamedSource::new("", source.to_string());
+        let tokens = Scanner::scan(source.to_string(), named_source, false).unwrap();
+        let statements = Optimizer::optimize(Parser::parse(tokens, false).unwrap(), false);
+        let crate::ast::stmt::StmtType::Print(expr) = &statements[0].stmt_type else {
+            panic!("not a print statement")
+        };
+        let crate::ast::expr::ExprType::Binary(left, _, _) = &expr.expr_type else {
+            panic!("not a binary expression")
+        };
+        assert_eq!(left.to_string(), "(6)");
+        assert_eq!(left.location, (6, 11).into());
+    }
+
+    #[test]
+    fn remove_dead_branches() {
+        assert_eq!(optimize("if (1 > 2) print 1; else print 2;"), "Print(2)\n");
+        assert_eq!(optimize("if (nil) print 1; print 2;"), "Print(2)\n");
+        assert_eq!(optimize("while (false) print 1;"), "");
+        assert_eq!(
+            optimize("for (var i = 0; false;) print i;"),
+            "{\nVar i = (0)\n}\n"
+        );
+    }
+
+    #[test]
+    fn replace_dead_nested_branch_with_empty_block() {
+        assert_eq!(
+            optimize("while (a) if (false) print 1;"),
+            "while (variable a) {\n{\n}\n}\n"
+        );
+    }
+}
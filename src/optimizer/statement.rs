@@ -0,0 +1,109 @@
+use crate::ast::{
+    expr::ExprType,
+    stmt::{Function, Stmt, StmtType},
+};
+
+use super::expr::optimize_expr;
+
+pub(super) fn optimize_statements(statements: Vec<Stmt>) -> Vec<Stmt> {
+    statements
+        .into_iter()
+        .filter_map(optimize_statement)
+        .collect()
+}
+
+/// `None` if the statement can never run.
+fn optimize_statement(statement: Stmt) -> Option<Stmt> {
+    use StmtType::*;
+    let Stmt {
+        stmt_type,
+        location,
+        src,
+    } = statement;
+    let stmt_type = match stmt_type {
+        Expression(expr) => Expression(optimize_expr(expr)),
+        Print(expr) => Print(optimize_expr(expr)),
+        Var {
+            name,
+            annotation,
+            initializer,
+        } => Var {
+            name,
+            annotation,
+            initializer: initializer.map(optimize_expr),
+        },
+        Function(function) => Function(optimize_function(function)),
+        Return(value) => Return(value.map(optimize_expr)),
+        Block(statements) => Block(optimize_statements(statements)),
+        If {
+            condition,
+            then_stmt,
+            else_stmt,
+        } => {
+            let condition = optimize_expr(condition);
+            if let ExprType::Literal(literal) = &condition.expr_type {
+                let branch = if literal.is_truthy() {
+                    Some(then_stmt)
+                } else {
+                    else_stmt
+                };
+                return branch.and_then(|branch| optimize_statement(*branch));
+            }
+            If {
+                condition,
+                then_stmt: Box::new(optimize_branch(*then_stmt)),
+                else_stmt: else_stmt.map(|branch| Box::new(optimize_branch(*branch))),
+            }
+        }
+        While {
+            condition,
+            body,
+            increment,
+        } => {
+            let condition = optimize_expr(condition);
+            if matches!(&condition.expr_type, ExprType::Literal(literal) if !literal.is_truthy()) {
+                return None;
+            }
+            While {
+                condition,
+                body: Box::new(optimize_branch(*body)),
+                increment: increment.map(optimize_expr),
+            }
+        }
+        Break => Break,
+        Continue => Continue,
+        Class {
+            name,
+            methods,
+            superclass,
+        } => Class {
+            name,
+            methods: methods.into_iter().map(optimize_function).collect(),
+            superclass,
+        },
+    };
+    Some(Stmt {
+        stmt_type,
+        location,
+        src,
+    })
+}
+
+/// The body of an `if` or `while` has to stay a statement, one that never runs becomes an
+/// empty block.
+fn optimize_branch(statement: Stmt) -> Stmt {
+    let location = statement.location;
+    let src = statement.src.clone();
+    optimize_statement(statement).unwrap_or(Stmt {
+        stmt_type: StmtType::Block(vec![]),
+        location,
+        src,
+    })
+}
+
+fn optimize_function(function: Function) -> Function {
+    Function {
+        body: optimize_statements(function.body),
+        ..function
+    }
+}
@@ -2,8 +2,9 @@ use std::vec;
 
 use miette::SourceSpan;
 
+use crate::ast::cst::SyntaxKind;
 use crate::ast::name::{Name, NameExpr};
-use crate::ast::stmt::{Function, Stmt, StmtType};
+use crate::ast::stmt::{Function, Stmt, StmtType, TypeAnnotation};
 use crate::ast::token::{Token, TokenType};
 use crate::source_span_extensions::SourceSpanExtensions;
 
@@ -25,20 +26,28 @@ impl Parser {
 
     pub(super) fn var_declaration(&mut self) -> Result<Stmt> {
         use TokenType::*;
+        self.start_node(SyntaxKind::VarDeclaration);
         let var_location = self.advance().location;
         let peek = self.peek();
         if let Identifier(name) = &peek.token_type {
-            let name = name.clone();
+            let name = NameExpr::new(name.clone().into(), peek.location, self.src.clone());
             self.advance();
+            let annotation = self.type_annotation()?;
             let mut expr = None;
-            if match_token!(self, TokenType::Equal).is_some() {
-                expr = Some(self.expression()?)
+            if check!(self, Equal) {
+                self.start_node(SyntaxKind::Initializer);
+                self.advance();
+                expr = Some(self.expression()?);
+                self.finish_node();
             }
-            let semicolon = consume!(self, Semicolon, |t| self.expected_semicolon(t));
+            let semicolon_location =
+                consume!(self, Semicolon, |t| self.expected_semicolon(t)).location;
+            self.finish_node();
             Ok(Stmt::var(
                 name,
+                annotation,
                 expr,
-                var_location.until(semicolon.location),
+                var_location.until(semicolon_location),
                 self.src.clone(),
             ))
         } else {
@@ -50,8 +59,10 @@ impl Parser {
     }
 
     fn fun_declaration(&mut self) -> Result<Stmt> {
+        self.start_node(SyntaxKind::FunctionDeclaration);
         let fun_location = self.advance().location;
         let (function, location) = self.function(fun_location)?;
+        self.finish_node();
         Ok(Stmt {
             stmt_type: StmtType::Function(function),
             location,
@@ -63,10 +74,11 @@ impl Parser {
         use TokenType::*;
         let identifier = self.peek();
         if let Identifier(name) = &identifier.token_type {
-            let name = name.clone();
+            let name = NameExpr::new(name.clone().into(), identifier.location, self.src.clone());
             self.advance();
 
-            let parameters = self.parameter_list()?;
+            let (parameters, parameter_types) = self.parameter_list()?.into_iter().unzip();
+            let return_type = self.type_annotation()?;
 
             let left_brace = self.peek();
             if !matches!(left_brace.token_type, LeftBrace) {
@@ -80,8 +92,10 @@ impl Parser {
 
             Ok((
                 Function {
-                    name: name.into(),
-                    parameters: parameters.into_iter().map(|arg| arg.into()).collect(),
+                    name,
+                    parameters,
+                    parameter_types,
+                    return_type,
                     body: body.stmts,
                 },
                 start_location.until(body.location),
@@ -96,21 +110,21 @@ impl Parser {
 
     fn class_declaration(&mut self) -> Result<Stmt> {
         use TokenType::*;
+        self.start_node(SyntaxKind::ClassDeclaration);
         let class_location = self.advance().location;
         let identifier = self.peek();
         if let Identifier(name) = &identifier.token_type {
-            let name = name.clone();
+            let name = NameExpr::new(name.clone().into(), identifier.location, self.src.clone());
             self.advance();
-            let superclass = if match_token!(self, Less).is_some() {
+            let superclass = if check!(self, Less) {
+                self.start_node(SyntaxKind::Superclass);
+                self.advance();
                 let superclass_location = self.peek().location;
                 if let Identifier(name) = &self.peek().token_type {
                     let name = Name::new(name.clone());
                     self.advance();
-                    Some(NameExpr {
-                        name,
-                        location: superclass_location,
-                        src: self.src.clone(),
-                    })
+                    self.finish_node();
+                    Some(NameExpr::new(name, superclass_location, self.src.clone()))
                 } else {
                     Err(ExpectedSuperclass {
                         src: self.src.clone(),
@@ -130,7 +144,9 @@ impl Parser {
             let mut methods = vec![];
 
             while !check!(self, RightBrace) && !self.is_at_end() {
-                methods.push(self.function(self.peek().location)?.0)
+                self.start_node(SyntaxKind::Method);
+                methods.push(self.function(self.peek().location)?.0);
+                self.finish_node();
             }
 
             let right_brace = consume!(self, RightBrace, |t: &Token| {
@@ -140,6 +156,7 @@ impl Parser {
                 }
             });
             let location = class_location.until(right_brace.location);
+            self.finish_node();
             Ok(Stmt::class(
                 name,
                 methods,
@@ -155,8 +172,9 @@ impl Parser {
         }
     }
 
-    fn parameter_list(&mut self) -> Result<Vec<String>> {
+    fn parameter_list(&mut self) -> Result<Vec<(NameExpr, Option<TypeAnnotation>)>> {
         use TokenType::*;
+        self.start_node(SyntaxKind::Parameters);
         consume!(self, LeftParen, |t: &Token| {
             ExpectedLeftParen {
                 src: self.src.clone(),
@@ -176,8 +194,13 @@ impl Parser {
                 let identifier = self.peek();
                 let identifier_location = identifier.location;
                 if let Identifier(arg_name) = &identifier.token_type {
-                    parameters.push(arg_name.clone());
+                    let name = NameExpr::new(
+                        arg_name.clone().into(),
+                        identifier_location,
+                        self.src.clone(),
+                    );
                     self.advance();
+                    parameters.push((name, self.type_annotation()?));
                 } else {
                     return Err(ParserError::ExpectedIdentifier {
                         src: identifier.src.clone(),
@@ -196,8 +219,34 @@ impl Parser {
                 location: self.previous_if_eof(t.location),
             }
         });
+        self.finish_node();
         Ok(parameters)
     }
+
+    /// An optional `: Type` after a name or parameter list.
+    fn type_annotation(&mut self) -> Result<Option<TypeAnnotation>> {
+        use TokenType::*;
+        if !check!(self, Colon) {
+            return Ok(None);
+        }
+        self.start_node(SyntaxKind::TypeAnnotation);
+        self.advance();
+        let token = self.peek();
+        let Identifier(name) = &token.token_type else {
+            return Err(ExpectedType {
+                src: self.src.clone(),
+                location: self.previous_if_eof(token.location),
+            });
+        };
+        let ty = name.parse().map_err(|_| UnknownType {
+            name: name.clone(),
+            src: self.src.clone(),
+            location: token.location,
+        })?;
+        let location = self.advance().location;
+        self.finish_node();
+        Ok(Some(TypeAnnotation { ty, location }))
+    }
 }
 
 #[cfg(test)]
@@ -208,7 +257,7 @@ mod test {
             stmt::Stmt,
             token::{Token, TokenType},
         },
-        parser::test_helpers::*,
+        parser::{parser_error::ParserError, test_helpers::*},
     };
 
     use super::{Parser, Result};
@@ -313,4 +362,50 @@ mod test {
         let stmt = parse_declaration(tokens).unwrap();
         assert_eq!(stmt.to_string().trim_end(), "class A < B {\n}")
     }
+
+    #[test]
+    fn parse_type_annotations() {
+        let tokens = vec![
+            token(TokenType::Fun),
+            token(TokenType::Identifier("f".into())),
+            token(TokenType::LeftParen),
+            token(TokenType::Identifier("a".into())),
+            token(TokenType::Colon),
+            token(TokenType::Identifier("String".into())),
+            token(TokenType::Comma),
+            token(TokenType::Identifier("b".into())),
+            token(TokenType::RightParen),
+            token(TokenType::Colon),
+            token(TokenType::Identifier("Bool".into())),
+            token(TokenType::LeftBrace),
+            token(TokenType::Var),
+            token(TokenType::Identifier("x".into())),
+            token(TokenType::Colon),
+            token(TokenType::Identifier("Number".into())),
+            token(TokenType::Equal),
+            token(TokenType::Nil),
+            token(TokenType::Semicolon),
+            token(TokenType::RightBrace),
+            token(TokenType::Eof),
+        ];
+        let stmt = parse_declaration(tokens).unwrap();
+        assert_eq!(
+            stmt.to_string().trim_end(),
+            "fun f(a: String, b, ): Boolean {\nVar x: Number = (nil)\n}"
+        )
+    }
+
+    #[test]
+    fn parse_unknown_type() {
+        let tokens = vec![
+            token(TokenType::Var),
+            token(TokenType::Identifier("x".into())),
+            token(TokenType::Colon),
+            token(TokenType::Identifier("Integer".into())),
+            token(TokenType::Semicolon),
+            token(TokenType::Eof),
+        ];
+        let err = parse_declaration(tokens).unwrap_err();
+        assert_matches!(err, ParserError::UnknownType { name, .. } if name == "Integer")
+    }
 }
@@ -1,9 +1,10 @@
 use miette::SourceSpan;
 
+use crate::ast::cst::SyntaxKind;
 use crate::ast::expr::Expr;
 use crate::ast::literal::Literal;
 use crate::ast::name::{Name, NameExpr};
-use crate::ast::token::Token;
+use crate::ast::token::{StringPart, Token};
 use crate::ast::{expr::ExprType, token::TokenType};
 use crate::parser::macros::{check, consume};
 use crate::parser::parser_error::ParserError;
@@ -11,6 +12,7 @@ use crate::source_span_extensions::SourceSpanExtensions;
 
 use super::parser_error::ParserError::*;
 
+use super::lossless::{whitespace, SyntaxTreeBuilder};
 use super::macros::match_token;
 use super::{Parser, Result};
 
@@ -20,9 +22,12 @@ impl Parser {
     }
 
     fn assignment(&mut self) -> Result<Expr> {
+        let checkpoint = self.checkpoint();
         let expr = self.or()?;
         if match_token!(self, TokenType::Equal).is_some() {
+            self.start_node_at(checkpoint, SyntaxKind::Assignment);
             let value = self.assignment()?;
+            self.finish_node();
             if let ExprType::Variable(name_expr) = expr.expr_type {
                 return Ok(Expr::assign(name_expr, value));
             } else if let ExprType::Get(object, name) = expr.expr_type {
@@ -31,6 +36,12 @@ impl Parser {
                     location: expr.location,
                     src: expr.src,
                 });
+            } else if let ExprType::Index(object, index) = expr.expr_type {
+                return Ok(Expr {
+                    expr_type: ExprType::SetIndex(object, index, Box::new(value)),
+                    location: expr.location,
+                    src: expr.src,
+                });
             }
             self.errors.push(InvalidAssignmentTarget {
                 src: expr.src.clone(),
@@ -43,9 +54,12 @@ impl Parser {
 
     fn or(&mut self) -> Result<Expr> {
         use TokenType::*;
+        let checkpoint = self.checkpoint();
         let mut expr = self.and()?;
         while let Some(token) = match_token!(self, Or).cloned() {
+            self.start_node_at(checkpoint, SyntaxKind::Logical);
             let right = self.and()?;
+            self.finish_node();
             expr = Expr::logical(expr, token, right);
         }
         Ok(expr)
@@ -53,9 +67,12 @@ impl Parser {
 
     fn and(&mut self) -> Result<Expr> {
         use TokenType::*;
+        let checkpoint = self.checkpoint();
         let mut expr = self.equality()?;
         while let Some(token) = match_token!(self, And).cloned() {
+            self.start_node_at(checkpoint, SyntaxKind::Logical);
             let right = self.equality()?;
+            self.finish_node();
             expr = Expr::logical(expr, token, right);
         }
         Ok(expr)
@@ -63,9 +80,12 @@ impl Parser {
 
     fn equality(&mut self) -> Result<Expr> {
         use TokenType::*;
+        let checkpoint = self.checkpoint();
         let mut expr = self.comparision()?;
         while let Some(token) = match_token!(self, BangEqual | EqualEqual).cloned() {
+            self.start_node_at(checkpoint, SyntaxKind::Binary);
             let right = self.comparision()?;
+            self.finish_node();
             expr = Expr::binary(expr, token, right)
         }
         Ok(expr)
@@ -73,11 +93,14 @@ impl Parser {
 
     fn comparision(&mut self) -> Result<Expr> {
         use TokenType::*;
+        let checkpoint = self.checkpoint();
         let mut expr = self.term()?;
         while let Some(token) =
             match_token!(self, Greater | GreaterEqual | Less | LessEqual).cloned()
         {
+            self.start_node_at(checkpoint, SyntaxKind::Binary);
             let right = self.term()?;
+            self.finish_node();
             expr = Expr::binary(expr, token, right)
         }
         Ok(expr)
@@ -85,9 +108,12 @@ impl Parser {
 
     fn term(&mut self) -> Result<Expr> {
         use TokenType::*;
+        let checkpoint = self.checkpoint();
         let mut expr = self.factor()?;
         while let Some(token) = match_token!(self, Minus | Plus).cloned() {
+            self.start_node_at(checkpoint, SyntaxKind::Binary);
             let right = self.factor()?;
+            self.finish_node();
             expr = Expr::binary(expr, token, right)
         }
         Ok(expr)
@@ -95,9 +121,12 @@ impl Parser {
 
     fn factor(&mut self) -> Result<Expr> {
         use TokenType::*;
+        let checkpoint = self.checkpoint();
         let mut expr = self.unary()?;
         while let Some(token) = match_token!(self, Slash | Star).cloned() {
+            self.start_node_at(checkpoint, SyntaxKind::Binary);
             let right = self.unary()?;
+            self.finish_node();
             expr = Expr::binary(expr, token, right)
         }
         Ok(expr)
@@ -105,8 +134,12 @@ impl Parser {
 
     fn unary(&mut self) -> Result<Expr> {
         use TokenType::*;
+        let checkpoint = self.checkpoint();
         if let Some(token) = match_token!(self, Bang | Minus).cloned() {
-            Ok(Expr::unary(token, self.unary()?))
+            self.start_node_at(checkpoint, SyntaxKind::Unary);
+            let right = self.unary()?;
+            self.finish_node();
+            Ok(Expr::unary(token, right))
         } else {
             self.call()
         }
@@ -114,25 +147,33 @@ impl Parser {
 
     fn call(&mut self) -> Result<Expr> {
         use TokenType::*;
+        let checkpoint = self.checkpoint();
         let mut expr = self.primary()?;
         loop {
-            if match_token!(self, LeftParen).is_some() {
+            if check!(self, LeftParen) {
+                self.start_node_at(checkpoint, SyntaxKind::Call);
+                self.start_node(SyntaxKind::Arguments);
+                self.advance();
                 expr = self.finish_call(expr)?;
+                self.finish_node();
+                self.finish_node();
+            } else if match_token!(self, LeftBracket).is_some() {
+                self.start_node_at(checkpoint, SyntaxKind::Index);
+                expr = self.finish_index(expr)?;
+                self.finish_node();
             } else if match_token!(self, Dot).is_some() {
+                self.start_node_at(checkpoint, SyntaxKind::Get);
                 let peek = self.peek();
                 if let Identifier(name) = &peek.token_type {
                     let identifier_location = peek.location;
                     let location = expr.location.until(identifier_location);
                     let name: Name = name.clone().into();
                     self.advance();
+                    self.finish_node();
                     expr = Expr {
                         expr_type: ExprType::Get(
                             Box::new(expr),
-                            NameExpr {
-                                name,
-                                location: identifier_location,
-                                src: self.src.clone(),
-                            },
+                            NameExpr::new(name, identifier_location, self.src.clone()),
                         ),
                         src: self.src.clone(),
                         location,
@@ -183,8 +224,98 @@ impl Parser {
         ))
     }
 
+    fn finish_index(&mut self, object: Expr) -> Result<Expr> {
+        let index = self.expression()?;
+        let right_bracket = consume!(self, TokenType::RightBracket, |t: &Token| {
+            ExpectedRightBracket {
+                src: t.src.clone(),
+                location: self.previous_if_eof(t.location),
+            }
+        });
+        let location = object.location.until(right_bracket.location);
+        Ok(Expr {
+            expr_type: ExprType::Index(Box::new(object), Box::new(index)),
+            location,
+            src: self.src.clone(),
+        })
+    }
+
+    fn finish_list(&mut self, left_bracket_location: SourceSpan) -> Result<Expr> {
+        use TokenType::*;
+        let mut elements = vec![];
+        if !check!(self, RightBracket) {
+            loop {
+                elements.push(self.expression()?);
+                if match_token!(self, Comma).is_none() {
+                    break;
+                }
+            }
+        }
+        let right_bracket = consume!(self, RightBracket, |t: &Token| {
+            ExpectedRightBracket {
+                src: t.src.clone(),
+                location: self.previous_if_eof(t.location),
+            }
+        });
+        Ok(Expr {
+            expr_type: ExprType::List(elements),
+            location: left_bracket_location.until(right_bracket.location),
+            src: self.src.clone(),
+        })
+    }
+
+    // in expression position a brace can only start a map literal, blocks are statements
+    fn finish_map(&mut self, left_brace_location: SourceSpan) -> Result<Expr> {
+        use TokenType::*;
+        let mut entries = vec![];
+        if !check!(self, RightBrace) {
+            loop {
+                self.start_node(SyntaxKind::MapEntry);
+                let key = self.expression()?;
+                consume!(self, Colon, |t: &Token| {
+                    ExpectedColon {
+                        src: t.src.clone(),
+                        location: self.previous_if_eof(t.location),
+                    }
+                });
+                let value = self.expression()?;
+                self.finish_node();
+                entries.push((key, value));
+                if match_token!(self, Comma).is_none() {
+                    break;
+                }
+            }
+        }
+        let right_brace = consume!(self, RightBrace, |t: &Token| {
+            ExpectedRightBrace {
+                src: t.src.clone(),
+                location: self.previous_if_eof(t.location),
+            }
+        });
+        Ok(Expr {
+            expr_type: ExprType::Map(entries),
+            location: left_brace_location.until(right_brace.location),
+            src: self.src.clone(),
+        })
+    }
+
     fn primary(&mut self) -> Result<Expr> {
         use TokenType::*;
+        let kind = match self.peek().token_type {
+            False | True | Nil | Number(_) | String(_) => Some(SyntaxKind::Literal),
+            Interpolation(_) => Some(SyntaxKind::Interpolation),
+            Identifier(_) => Some(SyntaxKind::Variable),
+            This => Some(SyntaxKind::This),
+            Super => Some(SyntaxKind::Super),
+            LeftParen => Some(SyntaxKind::Grouping),
+            LeftBracket => Some(SyntaxKind::List),
+            LeftBrace => Some(SyntaxKind::Map),
+            // not an expression, reported below
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            self.start_node(kind);
+        }
         let token = self.advance().clone();
         let expr = match token.token_type.clone() {
             False => Expr::literal(Literal::Boolean(false), &token),
@@ -192,6 +323,7 @@ impl Parser {
             Nil => Expr::literal(Literal::Nil, &token),
             Number(n) => Expr::literal(Literal::Number(n), &token),
             String(s) => Expr::literal(Literal::String(s), &token),
+            Interpolation(parts) => self.interpolation(parts, &token)?,
             Identifier(name) => Expr::variable(name, token),
             LeftParen => {
                 let expr = self.expression()?;
@@ -206,11 +338,13 @@ impl Parser {
                 Expr::new(ExprType::grouping(expr), location, token.src)
             }
             This => Expr {
-                expr_type: ExprType::This,
+                expr_type: ExprType::This(NameExpr::this(token.location, self.src.clone())),
                 location: token.location,
                 src: self.src.clone(),
             },
             Super => self.parse_super(token.location)?,
+            LeftBracket => self.finish_list(token.location)?,
+            LeftBrace => self.finish_map(token.location)?,
             Eof => Err(UnexpectedEof {
                 src: token.src.clone(),
                 location: (
@@ -224,6 +358,106 @@ impl Parser {
                 location: token.location,
             })?,
         };
+        self.finish_node();
+        Ok(expr)
+    }
+
+    /// The syntax tree gets the string as fragments from the start of the literal to `${` and
+    /// from `}` to the next `${` or the end, with the expressions in between.
+    fn interpolation(&mut self, parts: Vec<StringPart>, token: &Token) -> Result<Expr> {
+        let mut fragment = Token {
+            token_type: TokenType::StringFragment,
+            trailing_trivia: vec![],
+            ..token.clone()
+        };
+        let mut exprs = vec![];
+        for part in parts {
+            match part {
+                StringPart::Literal(string, location) => exprs.push(Expr::new(
+                    ExprType::literal(Literal::String(string)),
+                    location,
+                    token.src.clone(),
+                )),
+                StringPart::Expression(tokens, location) => {
+                    let expression_start = location.offset() + 2;
+                    let first_token_start = tokens
+                        .first()
+                        .map_or(expression_start, |token| token.location.offset());
+                    let fragment_start = fragment.location.offset();
+                    fragment.location = (fragment_start, expression_start - fragment_start).into();
+                    fragment.trailing_trivia =
+                        whitespace(&self.src, expression_start, first_token_start);
+                    self.push_token(fragment.clone());
+                    exprs.push(self.interpolated_expression(tokens, location)?);
+
+                    let closing_brace = location.offset() + location.len() - 1;
+                    fragment.location = (closing_brace, 0).into();
+                    fragment.leading_trivia = vec![];
+                }
+            }
+        }
+        let fragment_start = fragment.location.offset();
+        let end = token.location.offset() + token.location.len();
+        fragment.location = (fragment_start, end - fragment_start).into();
+        fragment.trailing_trivia = token.trailing_trivia.clone();
+        self.push_token(fragment);
+        Ok(Expr::new(
+            ExprType::Interpolation(exprs),
+            token.location,
+            token.src.clone(),
+        ))
+    }
+
+    /// Parses the tokens of one `${...}` on their own, the closing `}` stands in for Eof.
+    fn interpolated_expression(
+        &mut self,
+        mut tokens: Vec<Token>,
+        location: SourceSpan,
+    ) -> Result<Expr> {
+        if tokens.is_empty() {
+            return Err(ExpectedExpression {
+                src: self.src.clone(),
+                location,
+            });
+        }
+        let closing_brace = location.offset() + location.len() - 1;
+        if self.syntax_tree.is_some() {
+            for index in 0..tokens.len() {
+                let end = tokens[index].location.offset() + tokens[index].location.len();
+                let next_start = tokens
+                    .get(index + 1)
+                    .map_or(closing_brace, |next| next.location.offset());
+                tokens[index].trailing_trivia = whitespace(&self.src, end, next_start);
+            }
+        }
+        tokens.push(Token::new(
+            TokenType::Eof,
+            (closing_brace, 0).into(),
+            self.src.clone(),
+        ));
+        let mut parser = Parser::new(tokens);
+        parser.syntax_tree = self.syntax_tree.as_ref().map(|_| SyntaxTreeBuilder::new());
+        let expr = parser.expression();
+        self.errors.append(&mut parser.errors);
+        // the input ran out at the closing brace, not at the end of the file
+        let expr = expr.map_err(|err| match err {
+            UnexpectedEof { src, .. } => ExpectedExpressionBeforeBrace {
+                src,
+                location: (closing_brace, 1).into(),
+            },
+            err => err,
+        })?;
+        if !parser.is_at_end() {
+            return Err(ExpectedRightBrace {
+                src: self.src.clone(),
+                location: parser.peek().location,
+            });
+        }
+        if let (Some(syntax_tree), Some(expression_tree)) =
+            (&mut self.syntax_tree, parser.syntax_tree)
+        {
+            syntax_tree.append(expression_tree);
+        }
         Ok(expr)
     }
 
@@ -239,11 +473,7 @@ impl Parser {
             let location = self.peek().location;
             let name = name.clone().into();
             self.advance();
-            NameExpr {
-                name,
-                location,
-                src: self.src.clone(),
-            }
+            NameExpr::new(name, location, self.src.clone())
         } else {
             return Err(ExpectedIdentifier {
                 src: self.src.clone(),
@@ -252,7 +482,10 @@ impl Parser {
         };
         let location = super_location.until(method.location);
         Ok(Expr {
-            expr_type: ExprType::Super(method),
+            expr_type: ExprType::Super(
+                NameExpr::super_name(super_location, self.src.clone()),
+                method,
+            ),
             location,
             src: self.src.clone(),
         })
@@ -264,8 +497,8 @@ mod test {
 
     use crate::{
         ast::{
-            expr::Expr,
-            token::{Token, TokenType},
+            expr::{Expr, ExprType},
+            token::{StringPart, Token, TokenType},
         },
         parser::{parser_error::ParserError, test_helpers::*},
     };
@@ -496,4 +729,102 @@ mod test {
         let expr = parse_expr(tokens).unwrap();
         assert_eq!(expr.to_string().trim_end(), "(super.name)")
     }
+
+    #[test]
+    fn parse_list_literal() {
+        let tokens = vec![
+            token(TokenType::LeftBracket),
+            token(TokenType::Number(1.0)),
+            token(TokenType::Comma),
+            token(TokenType::Nil),
+            token(TokenType::RightBracket),
+            token(TokenType::Eof),
+        ];
+        let expr = parse_expr(tokens).unwrap();
+        assert_eq!(expr.to_string().trim_end(), "(List (1), (nil), )")
+    }
+
+    #[test]
+    fn parse_interpolation() {
+        let parts = vec![
+            StringPart::Literal("a = ".into(), (1, 4).into()),
+            StringPart::Expression(
+                vec![
+                    token_with_location(TokenType::Identifier("a".into()), (7, 1).into()),
+                    token_with_location(TokenType::Plus, (9, 1).into()),
+                    token_with_location(TokenType::Number(1.0), (11, 1).into()),
+                ],
+                (5, 8).into(),
+            ),
+        ];
+        let tokens = vec![
+            token_with_location(TokenType::Interpolation(parts), (0, 14).into()),
+            token(TokenType::Eof),
+        ];
+        let expr = parse_expr(tokens).unwrap();
+        assert_eq!(
+            expr.to_string().trim_end(),
+            r#"(Interpolation ("a = "), (+ (variable a) (1)), )"#
+        );
+        assert_matches!(&expr.expr_type, ExprType::Interpolation(parts) if parts[1].location == (7, 5).into());
+    }
+
+    #[test]
+    fn parse_map_literal() {
+        let tokens = vec![
+            token(TokenType::LeftBrace),
+            token(TokenType::String("key".into())),
+            token(TokenType::Colon),
+            token(TokenType::Number(1.0)),
+            token(TokenType::RightBrace),
+            token(TokenType::Eof),
+        ];
+        let expr = parse_expr(tokens).unwrap();
+        assert_eq!(expr.to_string().trim_end(), r#"(Map ("key"): (1), )"#)
+    }
+
+    #[test]
+    fn parse_map_literal_missing_colon() {
+        let tokens = vec![
+            token(TokenType::LeftBrace),
+            token(TokenType::String("key".into())),
+            token(TokenType::Number(1.0)),
+            token(TokenType::RightBrace),
+            token(TokenType::Eof),
+        ];
+        let err = parse_expr(tokens).unwrap_err();
+        assert_matches!(err, ParserError::ExpectedColon { .. })
+    }
+
+    #[test]
+    fn parse_set_index() {
+        let name: String = "name".into();
+        let tokens = vec![
+            token(TokenType::Identifier(name.clone())),
+            token(TokenType::LeftBracket),
+            token(TokenType::Number(0.0)),
+            token(TokenType::RightBracket),
+            token(TokenType::Equal),
+            token(TokenType::True),
+            token(TokenType::Eof),
+        ];
+        let expr = parse_expr(tokens).unwrap();
+        assert_eq!(
+            expr.to_string().trim_end(),
+            "(SetIndex (variable name)[(0)] = (true))"
+        )
+    }
+
+    #[test]
+    fn parse_unclosed_index() {
+        let name: String = "name".into();
+        let tokens = vec![
+            token(TokenType::Identifier(name.clone())),
+            token(TokenType::LeftBracket),
+            token(TokenType::Number(0.0)),
+            token(TokenType::Eof),
+        ];
+        let err = parse_expr(tokens).unwrap_err();
+        assert_matches!(err, ParserError::ExpectedRightBracket { .. })
+    }
 }
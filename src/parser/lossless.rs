@@ -0,0 +1,175 @@
+use std::sync::Arc;
+
+use miette::NamedSource;
+
+use crate::ast::{
+    cst::{SyntaxElement, SyntaxKind, SyntaxNode},
+    token::{Trivia, TriviaKind},
+};
+
+/// Collects the concrete syntax tree while [`super::Parser`] builds the AST. The parser starts
+/// and finishes nodes around its rules, every token it consumes is added to the innermost
+/// open node. The tree is only complete if parsing succeeded.
+pub(super) struct SyntaxTreeBuilder {
+    /// Nodes under construction, the innermost last.
+    open: Vec<(SyntaxKind, Vec<SyntaxElement>)>,
+}
+
+/// Position in the innermost open node, to wrap what is parsed after it into a node once it
+/// turns out to be the left operand of a binary expression, a callee or an assignment target.
+#[derive(Clone, Copy)]
+pub(super) struct Checkpoint(usize);
+
+impl SyntaxTreeBuilder {
+    pub(super) fn new() -> Self {
+        Self {
+            open: vec![(SyntaxKind::Program, vec![])],
+        }
+    }
+
+    pub(super) fn start_node(&mut self, kind: SyntaxKind) {
+        self.open.push((kind, vec![]))
+    }
+
+    pub(super) fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.children().len())
+    }
+
+    pub(super) fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        let children = self.children_mut().split_off(checkpoint.0);
+        self.open.push((kind, children))
+    }
+
+    pub(super) fn finish_node(&mut self) {
+        let (kind, children) = self.open.pop().expect("finished more nodes than started");
+        self.push(SyntaxNode::new(kind, children))
+    }
+
+    pub(super) fn push(&mut self, element: impl Into<SyntaxElement>) {
+        self.children_mut().push(element.into())
+    }
+
+    /// Adds the children of the root of `other` to the innermost open node.
+    pub(super) fn append(&mut self, other: SyntaxTreeBuilder) {
+        self.children_mut().extend(other.finish().children)
+    }
+
+    /// The root node, with everything that was pushed to it.
+    pub(super) fn finish(mut self) -> SyntaxNode {
+        let (kind, children) = self.open.swap_remove(0);
+        SyntaxNode::new(kind, children)
+    }
+
+    fn children(&self) -> &Vec<SyntaxElement> {
+        &self.open.last().expect("root node is never finished").1
+    }
+
+    fn children_mut(&mut self) -> &mut Vec<SyntaxElement> {
+        &mut self.open.last_mut().expect("root node is never finished").1
+    }
+}
+
+/// The source between `start` and `end` as trivia. Tokens in `${...}` are scanned without
+/// trivia, the gaps between them can only hold whitespace.
+pub(super) fn whitespace(src: &Arc<NamedSource<String>>, start: usize, end: usize) -> Vec<Trivia> {
+    (start < end)
+        .then(|| Trivia {
+            kind: TriviaKind::Whitespace,
+            text: src.inner()[start..end].to_string(),
+            location: (start, end - start).into(),
+        })
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use miette::NamedSource;
+
+    use crate::{
+        ast::cst::{SyntaxElement, SyntaxKind, SyntaxNode},
+        parser::Parser,
+        scanner::Scanner,
+    };
+
+    fn parse(source: &str) -> SyntaxNode {
+        let named_source = NamedSource::new("", source.to_string());
+        let tokens = Scanner::scan_with_trivia(source.to_string(), named_source).unwrap();
+        Parser::parse_lossless(tokens).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let source =
+            "// leading\n\nclass  A < B {\n  init(a,b) { this.a = a; } /* trailing */\n}\n\
+                      for (var i = 0;i<3; i = i + 1) {print [1, 2][i] ; }\n\
+                      if (a) print {\"a\": 1}; else {}\n  // end\n";
+        assert_eq!(parse(source).to_string(), source);
+    }
+
+    #[test]
+    fn keeps_surface_syntax() {
+        let program = parse("for (;;) break;");
+        let kinds = program.child_nodes().map(|n| n.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, vec![SyntaxKind::ForStatement]);
+        let for_statement = program.child_nodes().next().unwrap();
+        assert_eq!(for_statement.tokens().len(), 7);
+    }
+
+    #[test]
+    fn precedence() {
+        let program = parse("a = 1 + 2 * 3 or b;");
+        let statement = program.child_nodes().next().unwrap();
+        let assignment = statement.child_nodes().next().unwrap();
+        assert_eq!(assignment.kind, SyntaxKind::Assignment);
+        let value = assignment.child_nodes().nth(1).unwrap();
+        assert_eq!(value.kind, SyntaxKind::Logical);
+        assert_eq!(value.to_string(), "1 + 2 * 3 or b");
+    }
+
+    #[test]
+    fn left_associative() {
+        let program = parse("print a.b(1)[2] - 3 - 4;");
+        let print = program.child_nodes().next().unwrap();
+        let outer = print.child_nodes().next().unwrap();
+        assert_eq!(outer.kind, SyntaxKind::Binary);
+        let inner = outer.child_nodes().next().unwrap();
+        assert_eq!(inner.to_string(), "a.b(1)[2] - 3 ");
+        let index = inner.child_nodes().next().unwrap();
+        assert_eq!(index.kind, SyntaxKind::Index);
+        let call = index.child_nodes().next().unwrap();
+        assert_eq!(call.kind, SyntaxKind::Call);
+    }
+
+    #[test]
+    fn interpolation() {
+        let source = "print \"a ${ x + 1 } b ${y}\"; // end";
+        let program = parse(source);
+        assert_eq!(program.to_string(), source);
+        let print = program.child_nodes().next().unwrap();
+        let interpolation = print.child_nodes().next().unwrap();
+        assert_eq!(interpolation.kind, SyntaxKind::Interpolation);
+        let kinds = interpolation
+            .child_nodes()
+            .map(|n| n.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, vec![SyntaxKind::Binary, SyntaxKind::Variable]);
+        let fragments = interpolation
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                SyntaxElement::Token(token) => Some(token.text()),
+                SyntaxElement::Node(_) => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(fragments, vec!["\"a ${", "} b ${", "}\""]);
+    }
+
+    #[test]
+    fn reports_parser_errors() {
+        let source = "print 1";
+        let named_source = NamedSource::new("", source.to_string());
+        let tokens = Scanner::scan_with_trivia(source.to_string(), named_source).unwrap();
+        assert!(Parser::parse_lossless(tokens).is_err());
+    }
+}
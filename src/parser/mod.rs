@@ -1,5 +1,6 @@
 mod declaration;
 mod expression;
+mod lossless;
 mod macros;
 pub mod parser_error;
 mod statement;
@@ -9,6 +10,7 @@ use std::sync::Arc;
 use miette::{NamedSource, SourceSpan};
 
 use self::{
+    lossless::{Checkpoint, SyntaxTreeBuilder},
     macros::{check, consume},
     parser_error::{
         ParserError::{self, *},
@@ -17,6 +19,7 @@ use self::{
 };
 use crate::{
     ast::{
+        cst::{SyntaxKind, SyntaxNode},
         stmt::Stmt,
         token::{Token, TokenType},
     },
@@ -30,6 +33,8 @@ pub struct Parser {
     errors: Vec<ParserError>,
     current: usize,
     src: Arc<NamedSource<String>>,
+    /// Only set by [`Parser::parse_lossless`].
+    syntax_tree: Option<SyntaxTreeBuilder>,
 }
 struct InternalBlock {
     stmts: Vec<Stmt>,
@@ -50,6 +55,18 @@ impl Parser {
         Ok(stmts)
     }
 
+    /// Parses tokens scanned with [`crate::Scanner::scan_with_trivia`] into a tree that prints
+    /// back the exact source. Reports the same errors as [`Parser::parse`].
+    pub fn parse_lossless(tokens: Vec<Token>) -> core::result::Result<SyntaxNode, ParserErrors> {
+        let mut parser = Self::new(tokens);
+        parser.syntax_tree = Some(SyntaxTreeBuilder::new());
+        parser.do_parse()?;
+        let eof = parser.peek().clone();
+        let mut syntax_tree = parser.syntax_tree.expect("set above");
+        syntax_tree.push(eof); // Eof holds the trivia at the end of the file
+        Ok(syntax_tree.finish())
+    }
+
     fn new(tokens: Vec<Token>) -> Self {
         assert!(!tokens.is_empty());
         let src = tokens[0].src.clone();
@@ -58,6 +75,7 @@ impl Parser {
             errors: vec![],
             current: 0,
             src,
+            syntax_tree: None,
         }
     }
 
@@ -83,6 +101,7 @@ impl Parser {
     }
 
     fn block(&mut self) -> Result<InternalBlock> {
+        self.start_node(SyntaxKind::Block);
         let left_brace_location = self.advance().location;
         let mut stmts = vec![];
         while !check!(self, TokenType::RightBrace) && !self.is_at_end() {
@@ -94,12 +113,14 @@ impl Parser {
                 src: t.src.clone(),
                 location: self.previous_if_eof(t.location),
             }
-        });
+        })
+        .clone();
+        self.finish_node();
 
         Ok(InternalBlock {
             stmts,
             location: left_brace_location.until(right_brace.location),
-            src: right_brace.src.clone(),
+            src: right_brace.src,
         })
     }
 
@@ -131,10 +152,47 @@ impl Parser {
         let current = self.current;
         if !self.is_at_end() {
             self.current += 1;
+            let token = &self.tokens[current];
+            // interpolations are split up by Parser::interpolation
+            if let Some(syntax_tree) = &mut self.syntax_tree {
+                if !matches!(token.token_type, TokenType::Interpolation(_)) {
+                    syntax_tree.push(token.clone());
+                }
+            }
         }
         &self.tokens[current] // guaranteed by bounds check in advance
     }
 
+    // the syntax tree events are no-ops unless parsing losslessly
+
+    fn start_node(&mut self, kind: SyntaxKind) {
+        if let Some(syntax_tree) = &mut self.syntax_tree {
+            syntax_tree.start_node(kind)
+        }
+    }
+
+    fn checkpoint(&self) -> Option<Checkpoint> {
+        self.syntax_tree.as_ref().map(SyntaxTreeBuilder::checkpoint)
+    }
+
+    fn start_node_at(&mut self, checkpoint: Option<Checkpoint>, kind: SyntaxKind) {
+        if let (Some(syntax_tree), Some(checkpoint)) = (&mut self.syntax_tree, checkpoint) {
+            syntax_tree.start_node_at(checkpoint, kind)
+        }
+    }
+
+    fn finish_node(&mut self) {
+        if let Some(syntax_tree) = &mut self.syntax_tree {
+            syntax_tree.finish_node()
+        }
+    }
+
+    fn push_token(&mut self, token: Token) {
+        if let Some(syntax_tree) = &mut self.syntax_tree {
+            syntax_tree.push(token)
+        }
+    }
+
     fn is_at_end(&self) -> bool {
         matches!(self.peek().token_type, TokenType::Eof)
     }
@@ -214,17 +272,13 @@ mod test_helpers {
     use crate::ast::token::{Token, TokenType};
 
     pub(super) fn token(token_type: TokenType) -> Token {
-        Token {
-            token_type,
-            location: (1, 1).into(),
-            src: NamedSource::new("", String::new()).into(),
-        }
+        token_with_location(token_type, (1, 1).into())
     }
     pub(super) fn token_with_location(token_type: TokenType, location: SourceSpan) -> Token {
-        Token {
+        Token::new(
             token_type,
             location,
-            src: NamedSource::new("", String::new()).into(),
-        }
+            NamedSource::new("", String::new()).into(),
+        )
     }
 }
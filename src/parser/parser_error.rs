@@ -6,14 +6,14 @@ use crate::ast::expr::Expr;
 
 #[derive(thiserror::Error, Debug, Diagnostic)]
 pub enum ParserError {
-    #[error("Expected (")]
+    #[error("Expected '('")]
     ExpectedLeftParen {
         #[source_code]
         src: Arc<NamedSource<String>>,
         #[label("here")]
         location: SourceSpan,
     },
-    #[error("Expected )")]
+    #[error("Expected ')'")]
     ExpectedRightParen {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -21,7 +21,7 @@ pub enum ParserError {
         location: SourceSpan,
     },
 
-    #[error("Expected {{")]
+    #[error("Expected '{{'")]
     ExpectedLeftBrace {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -29,7 +29,7 @@ pub enum ParserError {
         location: SourceSpan,
     },
 
-    #[error("Expected }}")]
+    #[error("Expected '}}'")]
     ExpectedRightBrace {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -37,6 +37,22 @@ pub enum ParserError {
         location: SourceSpan,
     },
 
+    #[error("Expected ']'")]
+    ExpectedRightBracket {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Expected ':'")]
+    ExpectedColon {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
     #[error("Expected expression")]
     ExpectedExpression {
         #[source_code]
@@ -45,6 +61,14 @@ pub enum ParserError {
         location: SourceSpan,
     },
 
+    #[error("Expected expression before '}}'")]
+    ExpectedExpressionBeforeBrace {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
     #[error("Unexpected EOF")]
     UnexpectedEof {
         #[source_code]
@@ -55,7 +79,7 @@ pub enum ParserError {
 
     #[error("Expected ';'")]
     ExpectedSemicolon {
-        expr: Option<Expr>, //for interpreting expr in repl without
+        expr: Option<Box<Expr>>, //for interpreting expr in repl without
         #[source_code]
         src: Arc<NamedSource<String>>,
         #[label("; expected")]
@@ -101,6 +125,24 @@ pub enum ParserError {
         #[label("this one is one too many")]
         location: SourceSpan,
     },
+    #[error("Expected type")]
+    ExpectedType {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+    #[error("Unknown type \"{name}\"")]
+    #[diagnostic(help(
+        "Types are Number, String, Boolean, Nil, List, Map, Function, NativeFunction, Class and Instance"
+    ))]
+    UnknownType {
+        name: String,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("not a type")]
+        location: SourceSpan,
+    },
     #[error("Expected superclass name")]
     ExpectedSuperclass {
         #[source_code]
@@ -1,5 +1,6 @@
 use std::vec;
 
+use crate::ast::cst::SyntaxKind;
 use crate::ast::expr::{Expr, ExprType};
 use crate::ast::literal::Literal;
 use crate::ast::stmt::{Stmt, StmtType};
@@ -8,7 +9,7 @@ use crate::source_span_extensions::SourceSpanExtensions;
 
 use super::parser_error::ParserError::*;
 
-use super::macros::{check, consume, match_token};
+use super::macros::{check, consume};
 use super::{Parser, Result};
 
 impl Parser {
@@ -21,6 +22,8 @@ impl Parser {
             While => self.while_statement(),
             For => self.for_statement(),
             Return => self.return_statement(),
+            Break => self.loop_control_statement(StmtType::Break),
+            Continue => self.loop_control_statement(StmtType::Continue),
             _ => self.expression_statement(),
         }
     }
@@ -36,6 +39,7 @@ impl Parser {
 
     fn if_statement(&mut self) -> Result<Stmt> {
         use TokenType::*;
+        self.start_node(SyntaxKind::IfStatement);
         let if_location = self.advance().location;
 
         consume!(self, LeftParen, |t: &Token| {
@@ -56,10 +60,16 @@ impl Parser {
 
         let then_statement = self.statement()?;
 
-        let else_statement = match_token!(self, Else)
-            .is_some()
-            .then(|| self.statement())
-            .transpose()?;
+        let else_statement = if check!(self, Else) {
+            self.start_node(SyntaxKind::ElseBranch);
+            self.advance();
+            let else_statement = self.statement()?;
+            self.finish_node();
+            Some(else_statement)
+        } else {
+            None
+        };
+        self.finish_node();
 
         let end_location = else_statement
             .as_ref()
@@ -76,6 +86,7 @@ impl Parser {
 
     fn while_statement(&mut self) -> Result<Stmt> {
         use TokenType::*;
+        self.start_node(SyntaxKind::WhileStatement);
         let while_location = self.advance().location;
 
         consume!(self, LeftParen, |t: &Token| {
@@ -95,13 +106,15 @@ impl Parser {
         });
 
         let body = self.statement()?;
+        self.finish_node();
         let location = while_location.until(body.location);
-        Ok(Stmt::while_stmt(condition, body, location))
+        Ok(Stmt::while_stmt(condition, body, None, location))
     }
 
     // source locations for the parts are weird but should not be needed anyways
     fn for_statement(&mut self) -> Result<Stmt> {
         use TokenType::*;
+        self.start_node(SyntaxKind::ForStatement);
         let for_location = self.advance().location;
 
         consume!(self, LeftParen, |t: &Token| {
@@ -145,22 +158,11 @@ impl Parser {
             }
         });
 
-        let mut body = self.statement()?;
-
-        let location = for_location.until(body.location);
-        body = if let Some(increment) = increment {
-            let increment_location = increment.location;
-            Stmt {
-                stmt_type: StmtType::Block(vec![body, Stmt::expr(increment, increment_location)]),
-                location,
-                src: condition.src.clone(),
-            }
-        } else {
-            body
-        };
+        let body = self.statement()?;
+        self.finish_node();
 
         let location = for_location.until(body.location);
-        let mut while_statement = Stmt::while_stmt(condition, body, location);
+        let mut while_statement = Stmt::while_stmt(condition, body, increment, location);
 
         while_statement = if let Some(initializer) = initializer {
             Stmt {
@@ -175,29 +177,37 @@ impl Parser {
     }
 
     fn expression_statement(&mut self) -> Result<Stmt> {
+        self.start_node(SyntaxKind::ExpressionStatement);
         let expr = self.expression()?;
         let semicolon = consume!(self, TokenType::Semicolon, |t: &Token| {
             // only recover from expression if it is the top level expression
-            let expr = (expr.location.offset() == self.tokens[0].location.offset()).then_some(expr);
+            let expr = (expr.location.offset() == self.tokens[0].location.offset())
+                .then(|| Box::new(expr));
             ExpectedSemicolon {
                 expr,
                 src: t.src.clone(),
                 location: self.previous_if_eof(t.location),
             }
-        });
-        let location = expr.location.until(semicolon.location);
+        })
+        .location;
+        self.finish_node();
+        let location = expr.location.until(semicolon);
         Ok(Stmt::expr(expr, location))
     }
 
     fn print_statement(&mut self) -> Result<Stmt> {
+        self.start_node(SyntaxKind::PrintStatement);
         let print_token_location = self.advance().location;
         let expr = self.expression()?;
-        let semicolon = consume!(self, TokenType::Semicolon, |t| self.expected_semicolon(t));
-        let location = print_token_location.until(semicolon.location);
+        let semicolon_location =
+            consume!(self, TokenType::Semicolon, |t| self.expected_semicolon(t)).location;
+        self.finish_node();
+        let location = print_token_location.until(semicolon_location);
         Ok(Stmt::print(expr, location))
     }
 
     fn return_statement(&mut self) -> Result<Stmt> {
+        self.start_node(SyntaxKind::ReturnStatement);
         let return_token_location = self.advance().location;
         let expr = (!check!(self, TokenType::Semicolon))
             .then(|| self.expression())
@@ -205,12 +215,29 @@ impl Parser {
 
         let semicolon_location =
             consume!(self, TokenType::Semicolon, |t| self.expected_semicolon(t)).location;
+        self.finish_node();
         Ok(Stmt {
             stmt_type: StmtType::Return(expr),
             location: return_token_location.until(semicolon_location),
             src: self.src.clone(),
         })
     }
+
+    fn loop_control_statement(&mut self, stmt_type: StmtType) -> Result<Stmt> {
+        self.start_node(match stmt_type {
+            StmtType::Break => SyntaxKind::BreakStatement,
+            _ => SyntaxKind::ContinueStatement,
+        });
+        let keyword_location = self.advance().location;
+        let semicolon_location =
+            consume!(self, TokenType::Semicolon, |t| self.expected_semicolon(t)).location;
+        self.finish_node();
+        Ok(Stmt {
+            stmt_type,
+            location: keyword_location.until(semicolon_location),
+            src: self.src.clone(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -427,7 +454,36 @@ mod test {
         let stmt = parse_stmt(tokens).unwrap();
         assert_eq!(
             stmt.to_string().trim_end(),
-            "{\nVar name = (nil)\nwhile (== (variable name) (nil)) {\n{\nExpr(nil)\nExpr(name=(true))\n}\n}\n}"
+            "{\nVar name = (nil)\nwhile (== (variable name) (nil)) {\nExpr(nil)\nincrement (name=(true))\n}\n}"
         )
     }
+
+    #[test]
+    fn parse_break_and_continue() {
+        let tokens = vec![
+            token(TokenType::While),
+            token(TokenType::LeftParen),
+            token(TokenType::True),
+            token(TokenType::RightParen),
+            token(TokenType::LeftBrace),
+            token(TokenType::Continue),
+            token(TokenType::Semicolon),
+            token(TokenType::Break),
+            token(TokenType::Semicolon),
+            token(TokenType::RightBrace),
+            token(TokenType::Eof),
+        ];
+        let stmt = parse_stmt(tokens).unwrap();
+        assert_eq!(
+            stmt.to_string().trim_end(),
+            "while (true) {\n{\ncontinue\nbreak\n}\n}"
+        )
+    }
+
+    #[test]
+    fn parse_break_missing_semicolon() {
+        let tokens = vec![token(TokenType::Break), token(TokenType::Eof)];
+        let err = parse_stmt(tokens).unwrap_err();
+        assert_matches!(err, ParserError::ExpectedSemicolon { .. })
+    }
 }
@@ -0,0 +1,133 @@
+use std::{collections::HashMap, sync::Arc};
+
+use miette::{NamedSource, SourceSpan};
+
+use super::{resolution_error::ResolutionError, Resolver};
+use crate::ast::{
+    name::{Name, NameExpr},
+    stmt::{Function, Stmt, StmtType},
+};
+
+/// The parameter count of a function or class declaration.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Signature {
+    arity: usize,
+    location: SourceSpan,
+}
+
+/// A call of a declaration whose arity is known, checked once the whole program is resolved
+/// unless the variable has been assigned a different value.
+#[derive(Debug)]
+pub(super) struct StaticCall {
+    signature: Signature,
+    arguments: usize,
+    src: Arc<NamedSource<String>>,
+    location: SourceSpan,
+}
+
+impl Signature {
+    pub(super) fn of_function(function: &Function) -> Self {
+        Signature {
+            arity: function.parameters.len(),
+            location: function.name.location,
+        }
+    }
+
+    /// Classes are called with the arguments of `init`, a subclass without one inherits it.
+    pub(super) fn of_class(
+        name: &NameExpr,
+        methods: &[Function],
+        superclass: &Option<NameExpr>,
+    ) -> Option<Self> {
+        match methods.iter().find(|m| m.name.name == Name::init()) {
+            Some(init) => Some(Self::of_function(init)),
+            None if superclass.is_none() => Some(Signature {
+                arity: 0,
+                location: name.location,
+            }),
+            None => None,
+        }
+    }
+}
+
+/// Functions and classes declared exactly once at the top level.
+pub(super) fn global_signatures(statements: &[Stmt]) -> HashMap<Name, Signature> {
+    let mut declarations: HashMap<&Name, Vec<Option<Signature>>> = HashMap::new();
+    for statement in statements {
+        let (name, signature) = match &statement.stmt_type {
+            StmtType::Var { name, .. } => (&name.name, None),
+            StmtType::Function(function) => {
+                (&function.name.name, Some(Signature::of_function(function)))
+            }
+            StmtType::Class {
+                name,
+                methods,
+                superclass,
+            } => (&name.name, Signature::of_class(name, methods, superclass)),
+            _ => continue,
+        };
+        declarations.entry(name).or_default().push(signature);
+    }
+    declarations
+        .into_iter()
+        .filter_map(|(name, signatures)| match signatures[..] {
+            [Some(signature)] => Some((name.clone(), signature)),
+            _ => None,
+        })
+        .collect()
+}
+
+impl Resolver {
+    pub(super) fn set_signature(&mut self, name: &Name, signature: Option<Signature>) {
+        if let Some(declaration) = self.scopes.last_mut().and_then(|s| s.get_mut(name)) {
+            declaration.signature = signature;
+        }
+    }
+
+    /// The signature of the declaration `name` refers to at this point.
+    fn signature_of(&self, name: &Name) -> Option<Signature> {
+        match self.scopes.iter().rev().find_map(|scope| scope.get(name)) {
+            Some(declaration) => declaration.signature,
+            None => self.global_signatures.get(name).copied(),
+        }
+    }
+
+    pub(super) fn record_call(
+        &mut self,
+        callee: &NameExpr,
+        arguments: usize,
+        src: &Arc<NamedSource<String>>,
+        location: SourceSpan,
+    ) {
+        if let Some(signature) = self.signature_of(&callee.name) {
+            self.static_calls.push(StaticCall {
+                signature,
+                arguments,
+                src: src.clone(),
+                location,
+            })
+        }
+    }
+
+    pub(super) fn record_assignment(&mut self, name: &NameExpr) {
+        if let Some(signature) = self.signature_of(&name.name) {
+            self.reassigned.insert(signature.location);
+        }
+    }
+
+    pub(super) fn check_calls(&mut self) {
+        let calls = std::mem::take(&mut self.static_calls);
+        for call in calls {
+            let signature = call.signature;
+            if call.arguments != signature.arity && !self.reassigned.contains(&signature.location) {
+                self.error(ResolutionError::WrongArity {
+                    expected: signature.arity,
+                    actual: call.arguments,
+                    src: call.src,
+                    location: call.location,
+                    declaration: signature.location,
+                })
+            }
+        }
+    }
+}
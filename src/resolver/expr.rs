@@ -2,92 +2,112 @@ use std::sync::Arc;
 
 use miette::{NamedSource, SourceSpan};
 
-use super::{resolution_error::ResolutionError, ClassType, Resolver, Result};
+use super::{resolution_error::ResolutionError, ClassType, Resolver};
 use crate::ast::{
     expr::{Expr, ExprType::*},
     name::NameExpr,
 };
 
 impl Resolver {
-    pub(super) fn resolve_expr(&mut self, expression: &Expr) -> Result<()> {
-        match &expression.expr_type {
+    pub(super) fn resolve_expr(&mut self, expression: &mut Expr) {
+        let Expr {
+            expr_type,
+            location,
+            src,
+        } = expression;
+        match expr_type {
             Assign(name_expr, expr) => {
-                self.resolve_expr(expr)?;
+                self.resolve_expr(expr);
                 self.resolve_local(name_expr);
-                Ok(())
+                self.record_assignment(name_expr);
             }
             Binary(lhs, _, rhs) => {
-                self.resolve_expr(lhs)?;
+                self.resolve_expr(lhs);
                 self.resolve_expr(rhs)
             }
             Logical(lhs, _, rhs) => {
-                self.resolve_expr(lhs)?;
+                self.resolve_expr(lhs);
                 self.resolve_expr(rhs)
             }
             Grouping(expr) => self.resolve_expr(expr),
-            Literal(_) => Ok(()),
+            Literal(_) => (),
             Unary(_, expr) => self.resolve_expr(expr),
             Variable(name_expr) => self.resolve_var_expr(name_expr),
             Call(name, arguments) => {
-                self.resolve_expr(name)?;
-                arguments.iter().try_for_each(|e| self.resolve_expr(e))
+                if let Variable(callee) = &name.expr_type {
+                    self.record_call(callee, arguments.len(), src, *location);
+                }
+                self.resolve_expr(name);
+                arguments.iter_mut().for_each(|e| self.resolve_expr(e))
             }
             Get(expr, _) => self.resolve_expr(expr),
             Set(expr, _, object) => {
-                self.resolve_expr(expr)?;
+                self.resolve_expr(expr);
                 self.resolve_expr(object)
             }
-            This => self.resolve_this(expression.location, &expression.src),
-            Super(_) => self.resolve_super(expression.location, &expression.src),
+            This(this) => self.resolve_this(this),
+            Super(keyword, _) => self.resolve_super(keyword, *location, src),
+            List(elements) | Interpolation(elements) => {
+                elements.iter_mut().for_each(|e| self.resolve_expr(e))
+            }
+            Map(entries) => entries.iter_mut().for_each(|(key, value)| {
+                self.resolve_expr(key);
+                self.resolve_expr(value)
+            }),
+            Index(object, index) => {
+                self.resolve_expr(object);
+                self.resolve_expr(index)
+            }
+            SetIndex(object, index, value) => {
+                self.resolve_expr(object);
+                self.resolve_expr(index);
+                self.resolve_expr(value)
+            }
         }
     }
 
-    fn resolve_var_expr(&mut self, name_expr: &NameExpr) -> Result<()> {
-        if let Some(false) = self.scopes.last().and_then(|s| s.get(&name_expr.name)) {
-            Err(ResolutionError::InitializedWithSelf {
+    fn resolve_var_expr(&mut self, name_expr: &mut NameExpr) {
+        let declaration = self.scopes.last().and_then(|s| s.get(&name_expr.name));
+        if declaration.is_some_and(|d| !d.defined) {
+            self.error(ResolutionError::InitializedWithSelf {
                 name: name_expr.name.clone(),
                 src: name_expr.src.clone(),
                 location: name_expr.location,
             })
         } else {
             self.resolve_local(name_expr);
-            Ok(())
         }
     }
 
-    fn resolve_this(&mut self, location: SourceSpan, src: &Arc<NamedSource<String>>) -> Result<()> {
+    fn resolve_this(&mut self, this: &mut NameExpr) {
         if self.current_class.is_none() {
-            Err(ResolutionError::InvalidThis {
-                src: src.clone(),
-                location,
+            self.error(ResolutionError::InvalidThis {
+                src: this.src.clone(),
+                location: this.location,
             })
         } else {
-            let name_expr = NameExpr::this(location, src.clone());
-            self.resolve_local(&name_expr);
-            Ok(())
+            self.resolve_local(this);
         }
     }
 
+    /// Errors label the whole `super.method` expression at `location`.
     fn resolve_super(
         &mut self,
+        keyword: &mut NameExpr,
         location: SourceSpan,
         src: &Arc<NamedSource<String>>,
-    ) -> Result<()> {
+    ) {
         use ResolutionError::*;
         match self.current_class {
-            None => Err(SuperOutsideClass {
+            None => self.error(SuperOutsideClass {
                 src: src.clone(),
                 location,
             }),
-            Some(ClassType::Class) => Err(SuperWithoutSuperclass {
+            Some(ClassType::Class) => self.error(SuperWithoutSuperclass {
                 src: src.clone(),
                 location,
             }),
-            Some(ClassType::Subclass) => {
-                let name_expr = NameExpr::super_name(location, src.clone());
-                self.resolve_local(&name_expr);
-                Ok(())
-            }
+            Some(ClassType::Subclass) => self.resolve_local(keyword),
         }
     }
 }
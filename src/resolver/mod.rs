@@ -1,22 +1,47 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use miette::SourceSpan;
+mod arity;
 mod expr;
 pub mod resolution_error;
 mod statement;
 
 use crate::ast::{
     expr::Expr,
-    name::{Name, NameExpr},
-    stmt::Stmt,
+    name::{Name, NameExpr, Slot},
+    stmt::{Stmt, StmtType},
 };
 
-use self::resolution_error::ResolutionError;
+use self::{
+    arity::{global_signatures, Signature, StaticCall},
+    resolution_error::{ResolutionError, ResolutionErrors},
+};
 
 #[derive(Debug, Default)]
 pub struct Resolver {
-    locals: HashMap<NameExpr, usize>,
-    scopes: Vec<HashMap<Name, bool>>,
+    /// Resolved local variables, only dumped in verbose mode.
+    locals: Vec<NameExpr>,
+    errors: Vec<ResolutionError>,
+    /// Only in strict mode: the names a variable that is not local can refer to.
+    globals: Option<HashSet<Name>>,
+    global_signatures: HashMap<Name, Signature>,
+    static_calls: Vec<StaticCall>,
+    /// Declarations assigned another value, calls to them are not checked.
+    reassigned: HashSet<SourceSpan>,
+    scopes: Vec<HashMap<Name, Declaration>>,
     current_function: Option<FunctionType>,
     current_class: Option<ClassType>,
+    in_loop: bool,
+}
+
+#[derive(Debug)]
+struct Declaration {
+    /// `None` for the implicit `this` and `super`.
+    location: Option<SourceSpan>,
+    defined: bool,
+    signature: Option<Signature>,
+    /// The index of the value in the environment of the scope.
+    index: usize,
 }
 
 #[derive(Debug, PartialEq)]
@@ -32,58 +57,143 @@ enum ClassType {
     Subclass,
 }
 
-type Result<T> = std::result::Result<T, ResolutionError>;
+type Result = std::result::Result<(), ResolutionErrors>;
 
 impl Resolver {
-    pub fn resolve(statements: &[Stmt], verbose: bool) -> Result<HashMap<NameExpr, usize>> {
-        let mut resolver = Resolver::default();
-        resolver.resolve_statements(statements)?;
-        if verbose {
-            eprintln!("Locals:");
-            eprintln!("{:?}", resolver.locals);
-        }
-        Ok(resolver.locals)
+    /// Stores the [`Slot`] of every local variable in its [`NameExpr`].
+    pub fn resolve(statements: &mut [Stmt], verbose: bool) -> Result {
+        let mut resolver = Resolver {
+            global_signatures: global_signatures(statements),
+            ..Resolver::default()
+        };
+        resolver.resolve_statements(statements);
+        resolver.finish(verbose)
+    }
+
+    /// Like [`Resolver::resolve`], but also reports variables that are neither local, declared
+    /// at the top level of the program nor in `known_globals`, e.g. natives or the globals of
+    /// earlier REPL inputs.
+    pub fn resolve_strict(
+        statements: &mut [Stmt],
+        known_globals: impl IntoIterator<Item = Name>,
+        verbose: bool,
+    ) -> Result {
+        let mut globals: HashSet<Name> = known_globals.into_iter().collect();
+        globals.extend(statements.iter().filter_map(declared_global));
+        let mut resolver = Resolver {
+            globals: Some(globals),
+            global_signatures: global_signatures(statements),
+            ..Resolver::default()
+        };
+        resolver.resolve_statements(statements);
+        resolver.finish(verbose)
     }
 
-    pub fn resolve_expression(
-        expression: &Expr,
+    pub fn resolve_expression_strict(
+        expression: &mut Expr,
+        known_globals: impl IntoIterator<Item = Name>,
         verbose: bool,
-    ) -> Result<HashMap<NameExpr, usize>> {
+    ) -> Result {
+        let mut resolver = Resolver {
+            globals: Some(known_globals.into_iter().collect()),
+            ..Resolver::default()
+        };
+        resolver.resolve_expr(expression);
+        resolver.finish(verbose)
+    }
+
+    pub fn resolve_expression(expression: &mut Expr, verbose: bool) -> Result {
         let mut resolver = Resolver::default();
-        resolver.resolve_expr(expression)?;
+        resolver.resolve_expr(expression);
+        resolver.finish(verbose)
+    }
+
+    fn finish(mut self, verbose: bool) -> Result {
+        self.check_calls();
         if verbose {
-            eprintln!("{:?}", resolver.locals);
+            eprintln!("Locals:");
+            for local in &self.locals {
+                let slot = local.slot.expect("only resolved locals are collected");
+                eprintln!("{} at {}: {slot}", local.name, local.location.offset());
+            }
+        }
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ResolutionErrors {
+                resolution_errors: self.errors,
+            })
         }
-        Ok(resolver.locals)
     }
 
-    fn resolve_statements(&mut self, statements: &[Stmt]) -> Result<()> {
+    fn resolve_statements(&mut self, statements: &mut [Stmt]) {
         statements
-            .iter()
-            .try_for_each(|s| self.resolve_statement(s))
+            .iter_mut()
+            .for_each(|s| self.resolve_statement(s))
     }
 
-    fn declare(&mut self, name: &Name) {
-        if let Some(scope) = self.scopes.last_mut() {
-            scope.insert(name.clone(), false);
+    /// Resolution goes on after an error, so a single run reports all of them.
+    fn error(&mut self, error: ResolutionError) {
+        self.errors.push(error)
+    }
+
+    /// Locals get the next slot of their scope, globals are looked up by name.
+    fn declare(&mut self, name: &mut NameExpr) {
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+        let declaration = Declaration {
+            location: Some(name.location),
+            defined: false,
+            signature: None,
+            index: scope.len(),
+        };
+        name.slot = Some(Slot::new(0, declaration.index));
+        if let Some(previous) = scope.insert(name.name.clone(), declaration) {
+            self.error(ResolutionError::AlreadyDeclared {
+                name: name.name.clone(),
+                src: name.src.clone(),
+                location: name.location,
+                previous: previous.location.unwrap_or(name.location),
+            })
         }
     }
 
     fn define(&mut self, name: &Name) {
         if let Some(scope) = self.scopes.last_mut() {
-            scope.insert(name.clone(), true);
+            let index = scope.len();
+            scope
+                .entry(name.clone())
+                .or_insert(Declaration {
+                    location: None,
+                    defined: false,
+                    signature: None,
+                    index,
+                })
+                .defined = true;
         }
     }
 
-    fn resolve_local(&mut self, name_expr: &NameExpr) {
+    fn resolve_local(&mut self, name_expr: &mut NameExpr) {
         let resolved = self
             .scopes
             .iter()
             .rev()
             .enumerate()
-            .find(|(_index, scope)| scope.contains_key(&name_expr.name));
-        if let Some((index, _)) = resolved {
-            self.locals.insert(name_expr.clone(), index);
+            .find_map(|(depth, scope)| scope.get(&name_expr.name).map(|d| (depth, d.index)));
+        if let Some((depth, index)) = resolved {
+            name_expr.slot = Some(Slot::new(depth, index));
+            self.locals.push(name_expr.clone());
+        } else if self
+            .globals
+            .as_ref()
+            .is_some_and(|globals| !globals.contains(&name_expr.name))
+        {
+            self.error(ResolutionError::UndefinedVariable {
+                name: name_expr.name.clone(),
+                src: name_expr.src.clone(),
+                location: name_expr.location,
+            })
         }
     }
 
@@ -95,3 +205,11 @@ impl Resolver {
         self.scopes.pop();
     }
 }
+
+fn declared_global(statement: &Stmt) -> Option<Name> {
+    match &statement.stmt_type {
+        StmtType::Var { name, .. } | StmtType::Class { name, .. } => Some(name.name.clone()),
+        StmtType::Function(function) => Some(function.name.name.clone()),
+        _ => None,
+    }
+}
@@ -14,6 +14,36 @@ pub enum ResolutionError {
         #[label("here")]
         location: SourceSpan,
     },
+    #[error("\"{name}\" is already declared in this scope")]
+    AlreadyDeclared {
+        name: Name,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("declared again here")]
+        location: SourceSpan,
+        #[label("first declared here")]
+        previous: SourceSpan,
+    },
+    #[error("Undefined variable '{name}'")]
+    #[diagnostic(help("Declare it with var, fun or class at the top level"))]
+    UndefinedVariable {
+        name: Name,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+    #[error("Expected {expected} arguments but got {actual}")]
+    WrongArity {
+        expected: usize,
+        actual: usize,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("called here")]
+        location: SourceSpan,
+        #[label("declared here")]
+        declaration: SourceSpan,
+    },
     #[error("Can't return from top-level code")]
     InvalidReturn {
         #[source_code]
@@ -58,4 +88,27 @@ pub enum ResolutionError {
         #[label("here")]
         location: SourceSpan,
     },
+
+    #[error("Can't use 'break' outside of a loop")]
+    InvalidBreak {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Can't use 'continue' outside of a loop")]
+    InvalidContinue {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+}
+
+#[derive(thiserror::Error, Debug, Diagnostic)]
+#[error("Errors while resolving")]
+pub struct ResolutionErrors {
+    #[related]
+    pub resolution_errors: Vec<ResolutionError>,
 }
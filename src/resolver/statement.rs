@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use miette::{NamedSource, SourceSpan};
 
-use super::{resolution_error::ResolutionError, ClassType, FunctionType, Resolver, Result};
+use super::{
+    arity::Signature, resolution_error::ResolutionError, ClassType, Declaration, FunctionType,
+    Resolver,
+};
 use crate::ast::{
     expr::Expr,
     name::{Name, NameExpr},
@@ -10,15 +13,18 @@ use crate::ast::{
 };
 
 impl Resolver {
-    pub(super) fn resolve_statement(&mut self, statement: &Stmt) -> Result<()> {
-        match &statement.stmt_type {
+    pub(super) fn resolve_statement(&mut self, statement: &mut Stmt) {
+        match &mut statement.stmt_type {
             Expression(expr) => self.resolve_expr(expr),
             Print(expr) => self.resolve_expr(expr),
-            Var { name, initializer } => self.resolve_var(name, initializer),
+            Var {
+                name, initializer, ..
+            } => self.resolve_var(name, initializer),
             Function(function) => {
-                self.declare(&function.name);
-                self.define(&function.name);
-                self.resolve_function(&function.parameters, &function.body, FunctionType::Function)
+                self.declare(&mut function.name);
+                self.define(&function.name.name);
+                self.set_signature(&function.name.name, Some(Signature::of_function(function)));
+                self.resolve_function(function, FunctionType::Function)
             }
             Return(expr) => self.resolve_return(expr, statement.location, &statement.src),
             Block(statements) => self.resolve_block(statements),
@@ -27,14 +33,24 @@ impl Resolver {
                 then_stmt,
                 else_stmt,
             } => {
-                self.resolve_expr(condition)?;
-                self.resolve_statement(then_stmt)?;
-                else_stmt.iter().try_for_each(|s| self.resolve_statement(s))
-            }
-            While { condition, body } => {
-                self.resolve_expr(condition)?;
-                self.resolve_statement(body)
+                self.resolve_expr(condition);
+                self.resolve_statement(then_stmt);
+                else_stmt.iter_mut().for_each(|s| self.resolve_statement(s))
             }
+            While {
+                condition,
+                body,
+                increment,
+            } => self.resolve_while(condition, body, increment),
+            Break if !self.in_loop => self.error(ResolutionError::InvalidBreak {
+                src: statement.src.clone(),
+                location: statement.location,
+            }),
+            Continue if !self.in_loop => self.error(ResolutionError::InvalidContinue {
+                src: statement.src.clone(),
+                location: statement.location,
+            }),
+            Break | Continue => (),
             Class {
                 name,
                 methods,
@@ -43,49 +59,47 @@ impl Resolver {
         }
     }
 
-    fn resolve_function(
-        &mut self,
-        parameters: &[Name],
-        body: &[Stmt],
-        function_type: FunctionType,
-    ) -> Result<()> {
-        let enclosing_function = std::mem::replace(&mut self.current_function, Some(function_type));
+    /// The parameters take the first slots of the environment the body runs in.
+    fn resolve_function(&mut self, function: &mut Function, function_type: FunctionType) {
+        let enclosing_function = self.current_function.replace(function_type);
+        let enclosing_loop = std::mem::replace(&mut self.in_loop, false);
         self.begin_scope();
-        parameters.iter().for_each(|p| {
+        function.parameters.iter_mut().for_each(|p| {
             self.declare(p);
-            self.define(p);
+            self.define(&p.name);
         });
-        self.resolve_statements(body)?;
+        self.resolve_statements(&mut function.body);
         self.end_scope();
+        self.in_loop = enclosing_loop;
         self.current_function = enclosing_function;
-        Ok(())
     }
 
-    fn resolve_var(&mut self, name: &Name, initializer: &Option<Expr>) -> Result<()> {
+    fn resolve_var(&mut self, name: &mut NameExpr, initializer: &mut Option<Expr>) {
         self.declare(name);
-        initializer.iter().try_for_each(|e| self.resolve_expr(e))?;
-        self.define(name);
-        Ok(())
+        initializer.iter_mut().for_each(|e| self.resolve_expr(e));
+        self.define(&name.name);
     }
 
+    /// `super` and `this` are the only value in the environments wrapping the methods.
     fn resolve_class(
         &mut self,
-        name: &Name,
-        methods: &[Function],
-        superclass: &Option<NameExpr>,
-    ) -> Result<()> {
+        name: &mut NameExpr,
+        methods: &mut [Function],
+        superclass: &mut Option<NameExpr>,
+    ) {
         let class_type = if superclass.is_some() {
             ClassType::Subclass
         } else {
             ClassType::Class
         };
-        let enclosing_class = std::mem::replace(&mut self.current_class, Some(class_type));
+        let enclosing_class = self.current_class.replace(class_type);
         self.declare(name);
-        self.define(name);
+        self.define(&name.name);
+        self.set_signature(&name.name, Signature::of_class(name, methods, superclass));
 
         if let Some(superclass) = superclass {
-            if superclass.name == *name {
-                return Err(ResolutionError::SelfInheritance {
+            if superclass.name == name.name {
+                self.error(ResolutionError::SelfInheritance {
                     src: superclass.src.clone(),
                     location: superclass.location,
                 });
@@ -97,20 +111,26 @@ impl Resolver {
         if superclass.is_some() {
             self.begin_scope();
             let scope = self.scopes.last_mut().expect("scope declared above");
-            scope.insert(Name::super_name(), true);
+            let declaration = Declaration {
+                location: None,
+                defined: true,
+                signature: None,
+                index: 0,
+            };
+            scope.insert(Name::super_name(), declaration);
         }
 
         self.begin_scope();
 
         self.define(&Name::this());
-        methods.iter().try_for_each(|m| {
-            let function_type = if m.name == Name::init() {
+        methods.iter_mut().for_each(|m| {
+            let function_type = if m.name.name == Name::init() {
                 FunctionType::Initializer
             } else {
                 FunctionType::Method
             };
-            self.resolve_function(&m.parameters, &m.body, function_type)
-        })?;
+            self.resolve_function(m, function_type)
+        });
         self.end_scope();
 
         if superclass.is_some() {
@@ -118,42 +138,47 @@ impl Resolver {
         }
 
         self.current_class = enclosing_class;
-        Ok(())
     }
 
-    fn resolve_block(&mut self, statements: &[Stmt]) -> Result<()> {
+    fn resolve_while(
+        &mut self,
+        condition: &mut Expr,
+        body: &mut Stmt,
+        increment: &mut Option<Expr>,
+    ) {
+        self.resolve_expr(condition);
+        let enclosing_loop = std::mem::replace(&mut self.in_loop, true);
+        self.resolve_statement(body);
+        self.in_loop = enclosing_loop;
+        increment.iter_mut().for_each(|e| self.resolve_expr(e))
+    }
+
+    fn resolve_block(&mut self, statements: &mut [Stmt]) {
         self.begin_scope();
-        self.resolve_statements(statements)?;
+        self.resolve_statements(statements);
         self.end_scope();
-        Ok(())
     }
 
     fn resolve_return(
         &mut self,
-        expr: &Option<Expr>,
+        expr: &mut Option<Expr>,
         location: SourceSpan,
         src: &Arc<NamedSource<String>>,
-    ) -> Result<()> {
+    ) {
         if self.current_function.is_none() {
-            Err(ResolutionError::InvalidReturn {
+            self.error(ResolutionError::InvalidReturn {
                 src: src.clone(),
                 location,
             })
-        } else {
-            expr.iter().try_for_each(|e| {
-                if self
-                    .current_function
-                    .as_ref()
-                    .is_some_and(|c| *c == FunctionType::Initializer)
-                {
-                    Err(ResolutionError::ReturnInInitializer {
-                        src: src.clone(),
-                        location,
-                    })
-                } else {
-                    self.resolve_expr(e)
-                }
-            })
+        }
+        if let Some(expr) = expr {
+            if self.current_function == Some(FunctionType::Initializer) {
+                self.error(ResolutionError::ReturnInInitializer {
+                    src: src.clone(),
+                    location,
+                })
+            }
+            self.resolve_expr(expr)
         }
     }
 }
@@ -19,4 +19,6 @@ pub static KEYWORDS: phf::Map<&'static str, TokenType> = phf_map! {
     "var" => TokenType::Var,
     "while" => TokenType::While,
     "class" => TokenType::Class,
+    "break" => TokenType::Break,
+    "continue" => TokenType::Continue,
 };
@@ -1,40 +1,47 @@
 mod error_combiner;
 mod keywords;
+mod number;
 pub mod scanner_error;
 
-use std::sync::Arc;
+use std::{iter::Peekable, str::CharIndices, sync::Arc};
 
 use miette::NamedSource;
+use unicode_ident::{is_xid_continue, is_xid_start};
 
-use crate::ast::token::{Token, TokenType};
+use crate::ast::token::{StringPart, Token, TokenType, Trivia, TriviaKind};
 
 use {
     error_combiner::ErrorCombiner,
     keywords::KEYWORDS,
+    number::{expects_exponent_sign, parse_number},
     scanner_error::{
         ScannerError::{self, *},
         ScannerErrors,
     },
 };
 
-pub struct Scanner {
-    source: String,
+/// Offsets are in bytes so they can be used as [`miette::SourceSpan`]s directly.
+pub struct Scanner<'a> {
+    source: &'a str,
+    chars: Peekable<CharIndices<'a>>,
     named_source: Arc<NamedSource<String>>,
     tokens: Vec<Token>,
     start: usize,
-    current: usize,
     error_combiner: ErrorCombiner,
+    keep_trivia: bool,
+    pending_trivia: Vec<Trivia>,
+    newline_since_token: bool,
 }
 
 pub type Result<T> = core::result::Result<T, ScannerError>;
 
-impl Scanner {
+impl<'a> Scanner<'a> {
     pub fn scan(
         source: String,
         named_source: NamedSource<String>,
         verbose: bool,
     ) -> core::result::Result<Vec<Token>, ScannerErrors> {
-        let scan_tokens = Self::new(source, named_source).scan_tokens()?;
+        let scan_tokens = Scanner::new(&source, named_source).scan_tokens()?;
         if verbose {
             eprintln!("Tokens:");
             scan_tokens
@@ -45,36 +52,52 @@ impl Scanner {
         Ok(scan_tokens)
     }
 
-    fn new(source: String, named_source: NamedSource<String>) -> Self {
+    /// Like [`Scanner::scan`] but keeps whitespace and comments as trivia of the neighbouring
+    /// tokens, so the tokens add up to the exact source.
+    pub fn scan_with_trivia(
+        source: String,
+        named_source: NamedSource<String>,
+    ) -> core::result::Result<Vec<Token>, ScannerErrors> {
+        let mut scanner = Scanner::new(&source, named_source);
+        scanner.keep_trivia = true;
+        scanner.scan_tokens()
+    }
+
+    fn new(source: &'a str, named_source: NamedSource<String>) -> Self {
         let named_source: Arc<NamedSource<String>> = named_source.into();
         let error_combiner = ErrorCombiner::new(named_source.clone());
         Self {
             source,
+            chars: source.char_indices().peekable(),
             named_source,
             tokens: vec![],
             start: 0,
-            current: 0,
             error_combiner,
+            keep_trivia: false,
+            pending_trivia: vec![],
+            newline_since_token: false,
         }
     }
 
     fn scan_tokens(&mut self) -> core::result::Result<Vec<Token>, ScannerErrors> {
         let mut scanner_errors = vec![];
-        while let Some(char) = self.advance() {
-            self.start = self.current - 1; //has already been advanced
+        while let Some((start, char)) = self.chars.next() {
+            self.start = start;
             match self.scan_token(char) {
                 Ok(Some(token)) => self.add_token(token),
-                Ok(None) => (),
+                Ok(None) => self.add_trivia(),
                 Err(err) => scanner_errors.push(err),
             }
         }
-        self.tokens.push(Token::new(
+        let mut eof = Token::new(
             TokenType::Eof,
-            (self.current, 0).into(),
+            (self.source.len(), 0).into(),
             self.named_source.clone(),
-        ));
+        );
+        eof.leading_trivia = std::mem::take(&mut self.pending_trivia);
+        self.tokens.push(eof);
         if scanner_errors.is_empty() {
-            Ok(self.tokens.to_vec())
+            Ok(std::mem::take(&mut self.tokens))
         } else {
             let scanner_errors = self.error_combiner.combine(scanner_errors);
             Err(ScannerErrors { scanner_errors })
@@ -88,11 +111,14 @@ impl Scanner {
             ')' => Ok(Some(RightParen)),
             '{' => Ok(Some(LeftBrace)),
             '}' => Ok(Some(RightBrace)),
+            '[' => Ok(Some(LeftBracket)),
+            ']' => Ok(Some(RightBracket)),
             ',' => Ok(Some(Comma)),
             '.' => Ok(Some(Dot)),
             '-' => Ok(Some(Minus)),
             '+' => Ok(Some(Plus)),
             ';' => Ok(Some(Semicolon)),
+            ':' => Ok(Some(Colon)),
             '*' => Ok(Some(Star)),
             '!' => Ok(Some(if self.matches('=') { BangEqual } else { Bang })),
             '=' => Ok(Some(if self.matches('=') { EqualEqual } else { Equal })),
@@ -106,6 +132,9 @@ impl Scanner {
                 if self.matches('/') {
                     self.consume_comment();
                     Ok(None)
+                } else if self.matches('*') {
+                    self.consume_block_comment()?;
+                    Ok(None)
                 } else {
                     Ok(Some(Slash))
                 }
@@ -113,104 +142,300 @@ impl Scanner {
             ' ' | '\r' | '\t' | '\n' => Ok(None),
             '"' => self.read_string(),
             c if c.is_ascii_digit() => self.read_number(),
-            c if c.is_ascii_alphabetic() || c == '_' => Ok(Some(self.read_identifier())),
+            c if is_xid_start(c) || c == '_' => Ok(Some(self.read_identifier())),
 
             _ => Err(UnexpectedCharacter {
                 char,
                 src: self.named_source.clone(),
-                location: (self.current - 1, 1).into(),
+                location: (self.start, char.len_utf8()).into(),
             }),
         }
     }
 
     fn add_token(&mut self, token_type: TokenType) {
-        self.tokens.push(Token::new(
+        let current = self.current();
+        let mut token = Token::new(
             token_type,
-            (self.start, self.current - self.start).into(),
+            (self.start, current - self.start).into(),
             self.named_source.clone(),
-        ))
+        );
+        token.leading_trivia = std::mem::take(&mut self.pending_trivia);
+        self.newline_since_token = false;
+        self.tokens.push(token)
     }
 
-    fn advance(&mut self) -> Option<char> {
-        let char = self.source.chars().nth(self.current);
-        if char.is_some() {
-            self.current += 1;
+    /// Trivia up to the end of a token's line trails it, everything else leads the next token.
+    fn add_trivia(&mut self) {
+        if !self.keep_trivia {
+            return;
+        }
+        let source = self.source;
+        let text = &source[self.start..self.current()];
+        let kind = if text == "\n" {
+            TriviaKind::Newline
+        } else if text.starts_with("//") {
+            TriviaKind::LineComment
+        } else if text.starts_with("/*") {
+            TriviaKind::BlockComment
+        } else {
+            TriviaKind::Whitespace
+        };
+        let trivia_list = match self.tokens.last_mut() {
+            Some(token) if !self.newline_since_token && kind != TriviaKind::Newline => {
+                &mut token.trailing_trivia
+            }
+            _ => &mut self.pending_trivia,
+        };
+        match trivia_list.last_mut() {
+            Some(last) if last.kind == TriviaKind::Whitespace && kind == TriviaKind::Whitespace => {
+                last.text.push_str(text);
+                last.location = (last.location.offset(), last.location.len() + text.len()).into();
+            }
+            _ => trivia_list.push(Trivia {
+                kind,
+                text: text.to_string(),
+                location: (self.start, text.len()).into(),
+            }),
         }
-        char
+        if kind == TriviaKind::Newline {
+            self.newline_since_token = true;
+        }
+    }
+
+    /// Byte offset of the next character.
+    fn current(&mut self) -> usize {
+        self.chars
+            .peek()
+            .map_or(self.source.len(), |(offset, _)| *offset)
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        self.chars.next().map(|(_, char)| char)
     }
 
     fn matches(&mut self, expected: char) -> bool {
-        match self.source.chars().nth(self.current) {
-            Some(x) if x == expected => {
-                self.current += 1;
-                true
-            }
-            _ => false,
-        }
+        self.chars.next_if(|(_, char)| *char == expected).is_some()
     }
 
-    fn peek(&self) -> Option<char> {
-        self.source.chars().nth(self.current)
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, char)| *char)
     }
 
     fn peek_next(&self) -> Option<char> {
-        self.source.chars().nth(self.current + 1)
+        self.chars.clone().nth(1).map(|(_, char)| char)
+    }
+
+    fn advance_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.chars.next_if(|(_, char)| predicate(*char)).is_some() {}
     }
 
     fn consume_comment(&mut self) {
-        while let Some(x) = self.peek() {
-            if x == '\n' {
-                break;
-            } else {
-                self.advance();
+        self.advance_while(|char| char != '\n')
+    }
+
+    /// Block comments nest, so `/* /* */ */` is a single comment.
+    fn consume_block_comment(&mut self) -> Result<()> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.advance() {
+                Some('/') if self.matches('*') => depth += 1,
+                Some('*') if self.matches('/') => depth -= 1,
+                Some(_) => (),
+                None => Err(NonTerminatedComment {
+                    src: self.named_source.clone(),
+                    location: (self.start, 2).into(),
+                })?,
             }
         }
+        Ok(())
     }
 
     fn read_string(&mut self) -> Result<Option<TokenType>> {
-        let start = self.current;
+        let string_start = self.start;
+        let mut parts = vec![];
+        let mut string = String::new();
+        let mut part_start = string_start + 1;
+        // keep scanning to the closing quote so the rest of the literal is not read as code
+        let mut first_error = None;
         loop {
-            match self.peek() {
-                Some('"') => break,
-                Some(_) => self.current += 1,
+            match self.chars.next() {
+                Some((offset, '"')) => {
+                    if !parts.is_empty() && !string.is_empty() {
+                        parts.push(StringPart::Literal(
+                            string,
+                            (part_start, offset - part_start).into(),
+                        ));
+                        string = String::new();
+                    }
+                    break;
+                }
+                Some((offset, '\\')) => match self.read_escape(offset) {
+                    Ok(char) => string.push(char),
+                    Err(err) => {
+                        first_error.get_or_insert(err);
+                    }
+                },
+                Some((offset, '$')) if self.matches('{') => {
+                    if !string.is_empty() {
+                        let literal = std::mem::take(&mut string);
+                        parts.push(StringPart::Literal(
+                            literal,
+                            (part_start, offset - part_start).into(),
+                        ));
+                    }
+                    let tokens = self.read_interpolated_tokens(string_start, &mut first_error)?;
+                    part_start = self.current();
+                    parts.push(StringPart::Expression(
+                        tokens,
+                        (offset, part_start - offset).into(),
+                    ));
+                }
+                Some((_, char)) => string.push(char),
                 None => Err(NonTerminatedString {
                     src: self.named_source.clone(),
-                    location: (start - 1, self.current - start + 1).into(),
+                    location: (string_start, self.source.len() - string_start).into(),
                 })?,
             }
         }
-        self.current += 1; // the closing ""
-        let string = self.source[self.start + 1..self.current - 1].to_string();
-        Ok(Some(TokenType::String(string)))
+        self.start = string_start;
+        match first_error {
+            Some(err) => Err(err),
+            None if parts.is_empty() => Ok(Some(TokenType::String(string))),
+            None => Ok(Some(TokenType::Interpolation(parts))),
+        }
     }
 
-    fn read_number(&mut self) -> Result<Option<TokenType>> {
-        while self.peek().is_some_and(|x| x.is_ascii_digit()) {
-            self.current += 1;
+    /// Scans the expression of `${...}` up to the matching `}`, which is consumed. Errors in
+    /// the expression are kept in `first_error` so scanning goes on to the end of the literal.
+    /// Comments are rejected, a line comment would swallow the closing `}` and quote.
+    fn read_interpolated_tokens(
+        &mut self,
+        string_start: usize,
+        first_error: &mut Option<ScannerError>,
+    ) -> Result<Vec<Token>> {
+        let mut tokens = vec![];
+        let mut depth = 0;
+        while let Some((start, char)) = self.chars.next() {
+            self.start = start;
+            if char == '/' && matches!(self.peek(), Some('/' | '*')) {
+                self.advance();
+                first_error.get_or_insert(CommentInInterpolation {
+                    src: self.named_source.clone(),
+                    location: (start, 2).into(),
+                });
+                continue;
+            }
+            let token_type = self.scan_token(char).unwrap_or_else(|err| {
+                first_error.get_or_insert(err);
+                None
+            });
+            match token_type {
+                Some(TokenType::RightBrace) if depth == 0 => return Ok(tokens),
+                Some(token_type) => {
+                    match token_type {
+                        TokenType::LeftBrace => depth += 1,
+                        TokenType::RightBrace => depth -= 1,
+                        _ => (),
+                    }
+                    let location = (start, self.current() - start).into();
+                    tokens.push(Token::new(token_type, location, self.named_source.clone()));
+                }
+                None => (),
+            }
+        }
+        Err(NonTerminatedString {
+            src: self.named_source.clone(),
+            location: (string_start, self.source.len() - string_start).into(),
+        })
+    }
+
+    fn read_escape(&mut self, start: usize) -> Result<char> {
+        match self.advance() {
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some('r') => Ok('\r'),
+            Some('0') => Ok('\0'),
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('$') => Ok('$'),
+            Some('u') => self.read_unicode_escape(start),
+            Some(escape) => Err(InvalidEscape {
+                escape,
+                src: self.named_source.clone(),
+                location: (start, 1 + escape.len_utf8()).into(),
+            }),
+            // reported as non terminated string
+            None => Ok('\\'),
+        }
+    }
+
+    fn read_unicode_escape(&mut self, start: usize) -> Result<char> {
+        if !self.matches('{') {
+            return Err(InvalidUnicodeEscape {
+                escape: "\\u".to_string(),
+                src: self.named_source.clone(),
+                location: (start, 2).into(),
+            });
         }
+        let digits_start = self.current();
+        self.advance_while(|c| c.is_ascii_hexdigit());
+        let digits_end = self.current();
+        if !self.matches('}') {
+            return Err(NonTerminatedUnicodeEscape {
+                src: self.named_source.clone(),
+                location: (start, digits_end - start).into(),
+            });
+        }
+        let end = self.current();
+        let digits = &self.source[digits_start..digits_end];
+        u32::from_str_radix(digits, 16)
+            .ok()
+            .filter(|_| digits.len() <= 6)
+            .and_then(char::from_u32)
+            .ok_or_else(|| InvalidUnicodeEscape {
+                escape: self.source[start..end].to_string(),
+                src: self.named_source.clone(),
+                location: (start, end - start).into(),
+            })
+    }
+
+    // letters and underscores are taken into the literal so that typos are reported as one
+    // invalid number instead of a number followed by an identifier
+    fn read_number(&mut self) -> Result<Option<TokenType>> {
+        self.read_number_part();
         if self.peek().is_some_and(|x| x == '.')
             && self.peek_next().is_some_and(|x| x.is_ascii_digit())
         {
-            self.current += 1; // the .
-            while self.peek().is_some_and(|x| x.is_ascii_digit()) {
-                self.current += 1;
-            }
+            self.advance(); // the .
+            self.read_number_part();
         }
-        let result = self.source[self.start..self.current]
-            .parse::<f64>()
-            .map(|f| Some(TokenType::Number(f)))?;
-        Ok(result)
+        let source = self.source;
+        let literal = &source[self.start..self.current()];
+        parse_number(literal)
+            .map(|n| Some(TokenType::Number(n)))
+            .ok_or_else(|| InvalidNumber {
+                literal: literal.to_string(),
+                src: self.named_source.clone(),
+                location: (self.start, literal.len()).into(),
+            })
     }
 
-    fn read_identifier(&mut self) -> TokenType {
-        while self
-            .peek()
-            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+    fn read_number_part(&mut self) {
+        self.advance_while(|x| x.is_ascii_alphanumeric() || x == '_');
+        let source = self.source;
+        if expects_exponent_sign(&source[self.start..self.current()])
+            && self.peek().is_some_and(|x| x == '+' || x == '-')
+            && self.peek_next().is_some_and(|x| x.is_ascii_digit())
         {
-            self.current += 1;
+            self.advance(); // the sign
+            self.advance_while(|x| x.is_ascii_alphanumeric() || x == '_');
         }
+    }
 
-        let text = &self.source[self.start..self.current];
+    fn read_identifier(&mut self) -> TokenType {
+        self.advance_while(is_xid_continue);
+        let current = self.current();
+        let text = &self.source[self.start..current];
         let token = KEYWORDS.get(text).cloned();
         token.unwrap_or(TokenType::Identifier(text.to_string()))
     }
@@ -228,7 +453,7 @@ mod test {
     #[test]
     fn parse_string() {
         let input = "\"test\"".to_string();
-        let mut scanner = Scanner::new(input.clone(), NamedSource::new("", input));
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
         let result = scanner.scan_tokens().unwrap();
         let head = &result[0].token_type;
         assert_matches!(head, String(x) if x == "test");
@@ -236,7 +461,7 @@ mod test {
     #[test]
     fn parse_float() {
         let input = "1.1".to_string();
-        let mut scanner = Scanner::new(input.clone(), NamedSource::new("", input));
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
         let result = scanner.scan_tokens().unwrap();
         assert_eq!(result.len(), 2);
         let head = &result[0].token_type;
@@ -245,7 +470,7 @@ mod test {
     #[test]
     fn parse_identifier() {
         let input = "variable_name".to_string();
-        let mut scanner = Scanner::new(input.clone(), NamedSource::new("", input.clone()));
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
         let result = scanner.scan_tokens().unwrap();
         let head = &result[0];
         let token_type = &head.token_type;
@@ -255,7 +480,7 @@ mod test {
     #[test]
     fn parse_for() {
         let input = "for".to_string();
-        let mut scanner = Scanner::new(input.clone(), NamedSource::new("", input));
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
         let result = scanner.scan_tokens().unwrap();
         let head = &result[0];
         let token_type = &head.token_type;
@@ -265,7 +490,7 @@ mod test {
     #[test]
     fn raise_error_on_unterminated_string() {
         let input = "1+1; \"12345".to_string();
-        let mut scanner = Scanner::new(input.clone(), NamedSource::new("", input));
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
         let acc = scanner.scan_tokens().unwrap_err();
         let result = acc.scanner_errors.first().unwrap();
         assert_matches!(result, ScannerError::NonTerminatedString {
@@ -277,7 +502,7 @@ mod test {
     #[test]
     fn raise_error_on_unexpected_char() {
         let input = "^".to_string();
-        let mut scanner = Scanner::new(input.clone(), NamedSource::new("", input));
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
         let acc = scanner.scan_tokens().unwrap_err();
         let result = acc.scanner_errors.first().unwrap();
         assert_matches!(result, ScannerError::UnexpectedCharacter {
@@ -289,7 +514,7 @@ mod test {
     #[test]
     fn combine_unexpected_chars() {
         let input = "^^^^".to_string();
-        let mut scanner = Scanner::new(input.clone(), NamedSource::new("", input));
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
         let acc = scanner.scan_tokens().unwrap_err();
         let result = acc.scanner_errors.first().unwrap();
         assert_matches!(result, ScannerError::UnexpectedCharacters {
@@ -302,7 +527,7 @@ mod test {
     #[test]
     fn combine_unexpected_chars_only_if_offsets_overlap() {
         let input = "^^ @@".to_string();
-        let mut scanner = Scanner::new(input.clone(), NamedSource::new("", input));
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
         let acc = scanner.scan_tokens().unwrap_err();
         let result1 = acc.scanner_errors.first().unwrap();
         let result2 = acc.scanner_errors.get(1).unwrap();
@@ -317,4 +542,97 @@ mod test {
              location,
          } if chars == "@@" && src.name() == "" && *location == (3,2).into());
     }
+
+    #[test]
+    fn multi_byte_string_spans_bytes() {
+        let input = "\"héllo 日本\" x".to_string();
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
+        let result = scanner.scan_tokens().unwrap();
+        assert_matches!(&result[0].token_type, String(x) if x == "héllo 日本");
+        assert_eq!(result[0].location, (0, 15).into());
+        assert_matches!(&result[1].token_type, Identifier(x) if x == "x");
+        assert_eq!(result[1].location, (16, 1).into());
+        assert_eq!(result[2].location, (17, 0).into());
+    }
+
+    #[test]
+    fn error_after_multi_byte_comment() {
+        let input = "// ünïcödé ☃\n^".to_string();
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
+        let acc = scanner.scan_tokens().unwrap_err();
+        let result = acc.scanner_errors.first().unwrap();
+        assert_matches!(result, ScannerError::UnexpectedCharacter {
+             char: '^',
+             location,
+             ..
+         } if *location == (19,1).into())
+    }
+
+    #[test]
+    fn unexpected_multi_byte_chars() {
+        let input = "1 ☃☃".to_string();
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
+        let acc = scanner.scan_tokens().unwrap_err();
+        let result = acc.scanner_errors.first().unwrap();
+        assert_matches!(result, ScannerError::UnexpectedCharacters {
+             chars,
+             location,
+             ..
+         } if chars == "☃☃" && *location == (2,6).into())
+    }
+
+    #[test]
+    fn unterminated_multi_byte_string() {
+        let input = "\"ü".to_string();
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
+        let acc = scanner.scan_tokens().unwrap_err();
+        let result = acc.scanner_errors.first().unwrap();
+        assert_matches!(result, ScannerError::NonTerminatedString {
+             location,
+             ..
+         } if *location == (0,3).into())
+    }
+
+    #[test]
+    fn attach_trivia() {
+        use crate::ast::token::TriviaKind;
+        let input = "// header\nvar a = 1; // one\n  /* two */ print a;".to_string();
+        let tokens = Scanner::scan_with_trivia(input.clone(), NamedSource::new("", input)).unwrap();
+        let kinds = |trivia: &[crate::ast::token::Trivia]| {
+            trivia.iter().map(|t| t.kind).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            kinds(&tokens[0].leading_trivia),
+            vec![TriviaKind::LineComment, TriviaKind::Newline]
+        );
+        assert_eq!(kinds(&tokens[1].leading_trivia), vec![]);
+        assert_eq!(
+            kinds(&tokens[1].trailing_trivia),
+            vec![TriviaKind::Whitespace]
+        );
+        assert_eq!(
+            kinds(&tokens[4].trailing_trivia),
+            vec![TriviaKind::Whitespace, TriviaKind::LineComment]
+        );
+        assert_eq!(
+            kinds(&tokens[5].leading_trivia),
+            vec![
+                TriviaKind::Newline,
+                TriviaKind::Whitespace,
+                TriviaKind::BlockComment,
+                TriviaKind::Whitespace
+            ]
+        );
+        assert_eq!(tokens[5].leading_trivia[2].text, "/* two */");
+    }
+
+    #[test]
+    fn trivia_is_dropped_by_default() {
+        let input = "a // comment\n b".to_string();
+        let mut scanner = Scanner::new(&input, NamedSource::new("", input.clone()));
+        let tokens = scanner.scan_tokens().unwrap();
+        assert!(tokens
+            .iter()
+            .all(|t| t.leading_trivia.is_empty() && t.trailing_trivia.is_empty()));
+    }
 }
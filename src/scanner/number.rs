@@ -0,0 +1,94 @@
+/// Value of a number literal: decimal with optional fraction and exponent, `0x` hexadecimal or
+/// `0b` binary. Underscores may separate digits.
+pub fn parse_number(literal: &str) -> Option<f64> {
+    if let Some(digits) = strip_radix_prefix(literal, 'x') {
+        parse_integer(digits, 16)
+    } else if let Some(digits) = strip_radix_prefix(literal, 'b') {
+        parse_integer(digits, 2)
+    } else {
+        separated_by_digits(literal, |c| c.is_ascii_digit())
+            .then(|| literal.replace('_', "").parse::<f64>().ok())
+            .flatten()
+            .filter(|n| n.is_finite())
+    }
+}
+
+/// Whether the literal might continue with the sign of an exponent, as in `1e-9`.
+pub fn expects_exponent_sign(literal: &str) -> bool {
+    strip_radix_prefix(literal, 'x').is_none()
+        && strip_radix_prefix(literal, 'b').is_none()
+        && literal.ends_with(['e', 'E'])
+}
+
+fn strip_radix_prefix(literal: &str, radix: char) -> Option<&str> {
+    literal
+        .strip_prefix('0')
+        .and_then(|rest| rest.strip_prefix([radix, radix.to_ascii_uppercase()]))
+}
+
+fn parse_integer(digits: &str, radix: u32) -> Option<f64> {
+    separated_by_digits(digits, |c| c.is_digit(radix))
+        .then(|| u64::from_str_radix(&digits.replace('_', ""), radix).ok())
+        .flatten()
+        .map(|n| n as f64)
+}
+
+// every underscore sits between two digits
+fn separated_by_digits(literal: &str, is_digit: impl Fn(char) -> bool) -> bool {
+    let chars: Vec<char> = literal.chars().collect();
+    !chars.is_empty()
+        && chars.iter().enumerate().all(|(i, c)| {
+            *c != '_'
+                || (i > 0
+                    && i + 1 < chars.len()
+                    && is_digit(chars[i - 1])
+                    && is_digit(chars[i + 1]))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_number;
+
+    #[test]
+    fn decimal() {
+        assert_eq!(parse_number("42"), Some(42.0));
+        assert_eq!(parse_number("1.5"), Some(1.5));
+        assert_eq!(parse_number("1_000_000"), Some(1_000_000.0));
+        assert_eq!(parse_number("1_000.000_1"), Some(1000.0001));
+    }
+
+    #[test]
+    fn exponent() {
+        assert_eq!(parse_number("1e3"), Some(1000.0));
+        assert_eq!(parse_number("2.5E-2"), Some(0.025));
+        assert_eq!(parse_number("1e+2"), Some(100.0));
+        assert_eq!(parse_number("1e"), None);
+        assert_eq!(parse_number("1e999"), None);
+    }
+
+    #[test]
+    fn hex_and_binary() {
+        assert_eq!(parse_number("0xff"), Some(255.0));
+        assert_eq!(parse_number("0XFF_FF"), Some(65535.0));
+        assert_eq!(parse_number("0b1010"), Some(10.0));
+        assert_eq!(parse_number("0b1_0"), Some(2.0));
+        assert_eq!(parse_number("0x"), None);
+        assert_eq!(parse_number("0b102"), None);
+        assert_eq!(parse_number("0xfg"), None);
+    }
+
+    #[test]
+    fn misplaced_underscores() {
+        assert_eq!(parse_number("1_"), None);
+        assert_eq!(parse_number("1__0"), None);
+        assert_eq!(parse_number("1_.5"), None);
+        assert_eq!(parse_number("1_e5"), None);
+        assert_eq!(parse_number("0x_ff"), None);
+    }
+
+    #[test]
+    fn trailing_letters() {
+        assert_eq!(parse_number("12abc"), None);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{num::ParseFloatError, sync::Arc};
+use std::sync::Arc;
 
 use miette::{Diagnostic, NamedSource, SourceSpan};
 
@@ -30,8 +30,65 @@ pub enum ScannerError {
         location: SourceSpan,
     },
 
-    #[error(transparent)]
-    ParseFloatError(#[from] ParseFloatError),
+    #[error("Non terminated comment")]
+    NonTerminatedComment {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Comments are not allowed inside ${{...}}")]
+    #[diagnostic(help("Move the comment out of the string"))]
+    CommentInInterpolation {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Invalid escape sequence \\{escape}")]
+    #[diagnostic(help("Valid escapes are \\n, \\t, \\r, \\0, \\\", \\\\, \\$ and \\u{{...}}"))]
+    InvalidEscape {
+        escape: char,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Non terminated unicode escape")]
+    #[diagnostic(help("Close the escape with }}"))]
+    NonTerminatedUnicodeEscape {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Invalid unicode escape {escape}")]
+    #[diagnostic(help(
+        "Unicode escapes are written as \\u{{...}} with 1 to 6 hex digits of a unicode scalar value"
+    ))]
+    InvalidUnicodeEscape {
+        escape: String,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
+
+    #[error("Invalid number {literal}")]
+    #[diagnostic(help(
+        "Numbers are written as 1_000, 1.5, 1e-9, 0xff or 0b101 with underscores only between digits"
+    ))]
+    InvalidNumber {
+        literal: String,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("here")]
+        location: SourceSpan,
+    },
 }
 
 #[derive(thiserror::Error, Debug, Diagnostic)]
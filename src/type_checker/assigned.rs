@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use crate::ast::{
+    expr::{Expr, ExprType},
+    name::Name,
+    stmt::{Stmt, StmtType},
+};
+
+/// Names assigned anywhere in `statements`. A call through such a name may reach another
+/// function than the declared one, even if the assignment comes later in the source.
+pub(super) fn assigned_names(statements: &[Stmt]) -> HashSet<Name> {
+    let mut names = HashSet::new();
+    statements
+        .iter()
+        .for_each(|statement| collect_statement(statement, &mut names));
+    names
+}
+
+fn collect_statement(statement: &Stmt, names: &mut HashSet<Name>) {
+    use StmtType::*;
+    match &statement.stmt_type {
+        Expression(expr) | Print(expr) => collect_expr(expr, names),
+        Var { initializer, .. } | Return(initializer) => {
+            if let Some(initializer) = initializer {
+                collect_expr(initializer, names);
+            }
+        }
+        Function(function) => function
+            .body
+            .iter()
+            .for_each(|statement| collect_statement(statement, names)),
+        Block(statements) => statements
+            .iter()
+            .for_each(|statement| collect_statement(statement, names)),
+        If {
+            condition,
+            then_stmt,
+            else_stmt,
+        } => {
+            collect_expr(condition, names);
+            collect_statement(then_stmt, names);
+            if let Some(else_stmt) = else_stmt {
+                collect_statement(else_stmt, names);
+            }
+        }
+        While {
+            condition,
+            body,
+            increment,
+        } => {
+            collect_expr(condition, names);
+            collect_statement(body, names);
+            if let Some(increment) = increment {
+                collect_expr(increment, names);
+            }
+        }
+        Break | Continue => (),
+        Class { methods, .. } => methods
+            .iter()
+            .flat_map(|method| &method.body)
+            .for_each(|statement| collect_statement(statement, names)),
+    }
+}
+
+fn collect_expr(expression: &Expr, names: &mut HashSet<Name>) {
+    use ExprType::*;
+    match &expression.expr_type {
+        Assign(name, value) => {
+            names.insert(name.name.clone());
+            collect_expr(value, names);
+        }
+        Binary(left, _, right) | Logical(left, _, right) | Index(left, right) => {
+            collect_expr(left, names);
+            collect_expr(right, names);
+        }
+        Grouping(expr) | Unary(_, expr) | Get(expr, _) => collect_expr(expr, names),
+        Call(callee, arguments) => {
+            collect_expr(callee, names);
+            arguments
+                .iter()
+                .for_each(|argument| collect_expr(argument, names));
+        }
+        Set(object, _, value) => {
+            collect_expr(object, names);
+            collect_expr(value, names);
+        }
+        SetIndex(object, index, value) => {
+            collect_expr(object, names);
+            collect_expr(index, names);
+            collect_expr(value, names);
+        }
+        List(elements) | Interpolation(elements) => elements
+            .iter()
+            .for_each(|element| collect_expr(element, names)),
+        Map(entries) => entries.iter().for_each(|(key, value)| {
+            collect_expr(key, names);
+            collect_expr(value, names);
+        }),
+        Literal(_) | Variable(_) | This(_) | Super(..) => (),
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    ast::{
+        expr::{Expr, ExprType},
+        literal::Literal,
+        token::{Token, TokenType},
+    },
+    interpreter::types::Type,
+};
+
+use super::{type_error::TypeError, StaticType, TypeChecker};
+
+impl TypeChecker {
+    /// The type of the value of `expression` if it is known before running.
+    pub(super) fn check_expr(&mut self, expression: &Expr) -> Option<StaticType> {
+        use ExprType::*;
+        match &expression.expr_type {
+            Literal(literal) => Some(StaticType::inferred(literal_type(literal))),
+            Grouping(expr) => self.check_expr(expr),
+            Unary(token, expr) => {
+                let actual = self.check_expr(expr);
+                let ty = match token.token_type {
+                    TokenType::Minus => {
+                        self.expect_number(token, actual, expr);
+                        Type::Number
+                    }
+                    _ => Type::Boolean,
+                };
+                Some(derived(ty, actual, None))
+            }
+            Binary(left, token, right) => self.check_binary(left, token, right),
+            Logical(left, _, right) => {
+                let lhs = self.check_expr(left);
+                let rhs = self.check_expr(right);
+                let (lhs, rhs) = lhs.zip(rhs)?;
+                (lhs.ty == rhs.ty).then(|| derived(lhs.ty, Some(lhs), Some(rhs)))
+            }
+            Variable(name) => self.lookup(&name.name).and_then(|binding| {
+                binding
+                    .annotation
+                    .map(|annotation| StaticType::annotated(annotation.ty))
+                    .or(binding
+                        .callable
+                        .as_ref()
+                        .map(|callable| StaticType::inferred(callable.ty)))
+            }),
+            Assign(name, value) => {
+                let actual = self.check_expr(value);
+                let annotation = self
+                    .lookup(&name.name)
+                    .and_then(|binding| binding.annotation);
+                self.expect(annotation, actual, &value.src, value.location);
+                actual
+            }
+            Call(callee, arguments) => {
+                self.check_expr(callee);
+                let actual = arguments
+                    .iter()
+                    .map(|argument| self.check_expr(argument))
+                    .collect::<Vec<_>>();
+                let Variable(name) = &callee.expr_type else {
+                    return None;
+                };
+                let callable = self.lookup(&name.name)?.callable.clone()?;
+                for ((argument, actual), annotation) in
+                    arguments.iter().zip(actual).zip(callable.parameter_types)
+                {
+                    self.expect(annotation, actual, &argument.src, argument.location);
+                }
+                callable.returns
+            }
+            Get(object, _) => {
+                self.check_expr(object);
+                None
+            }
+            Set(object, _, value) => {
+                self.check_expr(object);
+                self.check_expr(value)
+            }
+            This(_) => Some(StaticType::inferred(Type::Instance)),
+            Super(..) => Some(StaticType::inferred(Type::Function)),
+            List(elements) => {
+                elements.iter().for_each(|element| {
+                    self.check_expr(element);
+                });
+                Some(StaticType::inferred(Type::List))
+            }
+            Map(entries) => {
+                entries.iter().for_each(|(key, value)| {
+                    self.check_expr(key);
+                    self.check_expr(value);
+                });
+                Some(StaticType::inferred(Type::Map))
+            }
+            Index(object, index) => {
+                self.check_expr(object);
+                self.check_expr(index);
+                None
+            }
+            SetIndex(object, index, value) => {
+                self.check_expr(object);
+                self.check_expr(index);
+                self.check_expr(value)
+            }
+            Interpolation(parts) => {
+                parts.iter().for_each(|part| {
+                    self.check_expr(part);
+                });
+                Some(StaticType::inferred(Type::String))
+            }
+        }
+    }
+
+    fn check_binary(&mut self, left: &Expr, token: &Token, right: &Expr) -> Option<StaticType> {
+        use TokenType::*;
+        let lhs = self.check_expr(left);
+        let rhs = self.check_expr(right);
+        let ty = match token.token_type {
+            Minus | Slash | Star => {
+                self.expect_number(token, lhs, left);
+                self.expect_number(token, rhs, right);
+                Type::Number
+            }
+            Greater | GreaterEqual | Less | LessEqual => {
+                self.expect_number(token, lhs, left);
+                self.expect_number(token, rhs, right);
+                Type::Boolean
+            }
+            BangEqual | EqualEqual => Type::Boolean,
+            Plus => match (lhs, rhs) {
+                (Some(l), Some(r)) if l.ty == r.ty && is_addable(l.ty) => l.ty,
+                (Some(l), Some(r)) => {
+                    if l.annotated || r.annotated {
+                        self.error(TypeError::PlusOperatorWrongTypes {
+                            actual_lhs: l.ty,
+                            actual_rhs: r.ty,
+                            src: token.src.clone(),
+                            operator_location: token.location,
+                            lhs: left.location,
+                            rhs: right.location,
+                        });
+                    }
+                    return None;
+                }
+                // the unknown operand has to be of the same type at runtime
+                (Some(known), None) | (None, Some(known)) if is_addable(known.ty) => known.ty,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(derived(ty, lhs, rhs))
+    }
+
+    fn expect_number(&mut self, token: &Token, actual: Option<StaticType>, operand: &Expr) {
+        if let Some(actual) = actual.filter(|t| t.annotated && t.ty != Type::Number) {
+            self.error(TypeError::WrongType {
+                operator: token.token_type.to_string(),
+                expected: Type::Number,
+                actual: actual.ty,
+                src: token.src.clone(),
+                operator_location: token.location,
+                operand_location: operand.location,
+            })
+        }
+    }
+}
+
+/// The type of an operation, annotated if one of its operands is.
+fn derived(ty: Type, lhs: Option<StaticType>, rhs: Option<StaticType>) -> StaticType {
+    StaticType {
+        ty,
+        annotated: [lhs, rhs].into_iter().flatten().any(|t| t.annotated),
+    }
+}
+
+fn is_addable(ty: Type) -> bool {
+    matches!(ty, Type::Number | Type::String)
+}
+
+fn literal_type(literal: &Literal) -> Type {
+    match literal {
+        Literal::String(_) => Type::String,
+        Literal::Number(_) => Type::Number,
+        Literal::Boolean(_) => Type::Boolean,
+        Literal::Nil => Type::Nil,
+    }
+}
@@ -0,0 +1,230 @@
+mod assigned;
+mod expr;
+mod statement;
+pub mod type_error;
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use miette::{NamedSource, SourceSpan};
+
+use crate::{
+    ast::{
+        expr::Expr,
+        name::Name,
+        stmt::{Function, Stmt, StmtType, TypeAnnotation},
+    },
+    interpreter::types::Type,
+};
+
+use self::type_error::{TypeError, TypeErrors};
+
+/// Checks the values given to annotated variables, parameters and returns, and the operands
+/// of operators typed by an annotation. Variables without initializer and functions ending
+/// without `return` give nil, so only a `Nil` annotation accepts them. Programs without
+/// annotations run unchanged and fail at runtime as before.
+#[derive(Debug)]
+pub struct TypeChecker {
+    errors: Vec<TypeError>,
+    scopes: Vec<HashMap<Name, Binding>>,
+    /// The annotated return type of the function being checked.
+    return_type: Option<TypeAnnotation>,
+    /// Calls through these names are not checked, they may reach another function.
+    reassigned: HashSet<Name>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Binding {
+    annotation: Option<TypeAnnotation>,
+    /// Functions and classes, calls to them are checked against their parameter types.
+    callable: Option<Callable>,
+}
+
+#[derive(Debug, Clone)]
+struct Callable {
+    ty: Type,
+    parameter_types: Vec<Option<TypeAnnotation>>,
+    /// The type of the value of a call.
+    returns: Option<StaticType>,
+}
+
+/// A type known before running.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StaticType {
+    ty: Type,
+    /// Follows from an annotation, only then operators with this operand are reported.
+    annotated: bool,
+}
+
+impl StaticType {
+    fn inferred(ty: Type) -> Self {
+        Self {
+            ty,
+            annotated: false,
+        }
+    }
+
+    fn annotated(ty: Type) -> Self {
+        Self {
+            ty,
+            annotated: true,
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, TypeErrors>;
+
+impl Default for TypeChecker {
+    fn default() -> Self {
+        Self {
+            errors: vec![],
+            scopes: vec![HashMap::new()],
+            return_type: None,
+            reassigned: HashSet::new(),
+        }
+    }
+}
+
+impl TypeChecker {
+    pub fn check(statements: &[Stmt]) -> Result<()> {
+        let mut checker = TypeChecker {
+            reassigned: assigned::assigned_names(statements),
+            ..TypeChecker::default()
+        };
+        checker.declare_globals(statements);
+        checker.check_statements(statements);
+        checker.finish()
+    }
+
+    pub fn check_expression(expression: &Expr) -> Result<()> {
+        let mut checker = TypeChecker::default();
+        checker.check_expr(expression);
+        checker.finish()
+    }
+
+    fn finish(self) -> Result<()> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(TypeErrors {
+                type_errors: self.errors,
+            })
+        }
+    }
+
+    /// Functions and classes can be called before their declaration at the top level.
+    fn declare_globals(&mut self, statements: &[Stmt]) {
+        let mut counts: HashMap<&Name, usize> = HashMap::new();
+        for statement in statements {
+            if let Some(name) = declared_name(statement) {
+                *counts.entry(name).or_default() += 1;
+            }
+        }
+        for statement in statements {
+            let callable = match &statement.stmt_type {
+                StmtType::Function(function) => Callable::function(function),
+                StmtType::Class { methods, .. } => Callable::class(methods),
+                _ => continue,
+            };
+            let name = declared_name(statement).expect("functions and classes have names");
+            if counts[name] == 1 {
+                self.declare(name, None, Some(callable));
+            }
+        }
+    }
+
+    fn check_statements(&mut self, statements: &[Stmt]) {
+        statements.iter().for_each(|s| self.check_statement(s))
+    }
+
+    /// Checking goes on after an error, so a single run reports all of them.
+    fn error(&mut self, error: TypeError) {
+        self.errors.push(error)
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare(
+        &mut self,
+        name: &Name,
+        annotation: Option<TypeAnnotation>,
+        callable: Option<Callable>,
+    ) {
+        let callable = callable.filter(|_| !self.reassigned.contains(name));
+        self.scopes
+            .last_mut()
+            .expect("the global scope is never ended")
+            .insert(
+                name.clone(),
+                Binding {
+                    annotation,
+                    callable,
+                },
+            );
+    }
+
+    fn lookup(&self, name: &Name) -> Option<&Binding> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Reports `actual` if it is known and not allowed where `annotation` is.
+    fn expect(
+        &mut self,
+        annotation: Option<TypeAnnotation>,
+        actual: Option<StaticType>,
+        src: &Arc<NamedSource<String>>,
+        location: SourceSpan,
+    ) {
+        if let (Some(annotation), Some(actual)) = (annotation, actual) {
+            if !accepts(annotation.ty, actual.ty) {
+                self.error(TypeError::Mismatch {
+                    expected: annotation.ty,
+                    actual: actual.ty,
+                    src: src.clone(),
+                    location,
+                    annotation: annotation.location,
+                })
+            }
+        }
+    }
+}
+
+impl Callable {
+    fn function(function: &Function) -> Self {
+        Callable {
+            ty: Type::Function,
+            parameter_types: function.parameter_types.clone(),
+            returns: function.return_type.map(|t| StaticType::annotated(t.ty)),
+        }
+    }
+
+    fn class(methods: &[Function]) -> Self {
+        let init = methods.iter().find(|m| m.name.name == Name::init());
+        Callable {
+            ty: Type::Class,
+            parameter_types: init.map_or(vec![], |init| init.parameter_types.clone()),
+            returns: Some(StaticType::inferred(Type::Instance)),
+        }
+    }
+}
+
+fn declared_name(statement: &Stmt) -> Option<&Name> {
+    match &statement.stmt_type {
+        StmtType::Var { name, .. } | StmtType::Class { name, .. } => Some(&name.name),
+        StmtType::Function(function) => Some(&function.name.name),
+        _ => None,
+    }
+}
+
+/// Natives can be used wherever a function is expected.
+fn accepts(expected: Type, actual: Type) -> bool {
+    expected == actual || (expected == Type::Function && actual == Type::NativeFunction)
+}
@@ -0,0 +1,165 @@
+use crate::{
+    ast::{
+        expr::ExprType,
+        literal::Literal,
+        name::Name,
+        stmt::{Function, Stmt, StmtType},
+    },
+    interpreter::types::Type,
+};
+
+use super::{type_error::TypeError, Callable, StaticType, TypeChecker};
+
+impl TypeChecker {
+    pub(super) fn check_statement(&mut self, statement: &Stmt) {
+        use StmtType::*;
+        match &statement.stmt_type {
+            Expression(expr) | Print(expr) => {
+                self.check_expr(expr);
+            }
+            Var {
+                name,
+                annotation,
+                initializer,
+            } => {
+                match (initializer, annotation) {
+                    (Some(initializer), _) => {
+                        let actual = self.check_expr(initializer);
+                        self.expect(*annotation, actual, &initializer.src, initializer.location);
+                    }
+                    (None, Some(annotation)) if annotation.ty != Type::Nil => {
+                        self.error(TypeError::Uninitialized {
+                            name: name.name.clone(),
+                            expected: annotation.ty,
+                            src: statement.src.clone(),
+                            annotation: annotation.location,
+                        })
+                    }
+                    (None, _) => (),
+                }
+                self.declare(&name.name, *annotation, None);
+            }
+            Function(function) => {
+                self.declare(
+                    &function.name.name,
+                    None,
+                    Some(Callable::function(function)),
+                );
+                self.check_function(function);
+            }
+            Return(value) => {
+                let actual = match value {
+                    Some(value) => self.check_expr(value),
+                    None => Some(StaticType::inferred(Type::Nil)),
+                };
+                self.expect(self.return_type, actual, &statement.src, statement.location);
+            }
+            Block(statements) => {
+                self.begin_scope();
+                self.check_statements(statements);
+                self.end_scope();
+            }
+            If {
+                condition,
+                then_stmt,
+                else_stmt,
+            } => {
+                self.check_expr(condition);
+                self.check_statement(then_stmt);
+                if let Some(else_stmt) = else_stmt {
+                    self.check_statement(else_stmt);
+                }
+            }
+            While {
+                condition,
+                body,
+                increment,
+            } => {
+                self.check_expr(condition);
+                self.check_statement(body);
+                if let Some(increment) = increment {
+                    self.check_expr(increment);
+                }
+            }
+            Break | Continue => (),
+            Class { name, methods, .. } => {
+                self.declare(&name.name, None, Some(Callable::class(methods)));
+                methods.iter().for_each(|m| self.check_function(m));
+            }
+        }
+    }
+
+    fn check_function(&mut self, function: &Function) {
+        let enclosing = std::mem::replace(&mut self.return_type, function.return_type);
+        self.begin_scope();
+        for (parameter, annotation) in function.parameters.iter().zip(&function.parameter_types) {
+            self.declare(&parameter.name, *annotation, None);
+        }
+        self.check_statements(&function.body);
+        self.end_scope();
+        self.return_type = enclosing;
+        self.check_falls_through(function);
+    }
+
+    /// Initializers return `this` at the end, every other function nil.
+    fn check_falls_through(&mut self, function: &Function) {
+        let Some(annotation) = function.return_type else {
+            return;
+        };
+        if annotation.ty == Type::Nil
+            || function.name.name == Name::init()
+            || always_returns(&function.body)
+        {
+            return;
+        }
+        self.error(TypeError::MissingReturn {
+            name: function.name.name.clone(),
+            expected: annotation.ty,
+            src: function.name.src.clone(),
+            location: function.name.location,
+            annotation: annotation.location,
+        })
+    }
+}
+
+/// Whether running `statements` ends in a `return` or never ends, conservatively.
+fn always_returns(statements: &[Stmt]) -> bool {
+    statements
+        .iter()
+        .any(|statement| match &statement.stmt_type {
+            StmtType::Return(_) => true,
+            StmtType::Block(statements) => always_returns(statements),
+            StmtType::If {
+                then_stmt,
+                else_stmt: Some(else_stmt),
+                ..
+            } => {
+                always_returns(std::slice::from_ref(then_stmt))
+                    && always_returns(std::slice::from_ref(else_stmt))
+            }
+            // `while (true)` only ends by a break
+            StmtType::While {
+                condition, body, ..
+            } => {
+                matches!(
+                    condition.expr_type,
+                    ExprType::Literal(Literal::Boolean(true))
+                ) && !breaks(body)
+            }
+            _ => false,
+        })
+}
+
+/// Whether `statement` contains a `break` out of the enclosing loop.
+fn breaks(statement: &Stmt) -> bool {
+    match &statement.stmt_type {
+        StmtType::Break => true,
+        StmtType::Block(statements) => statements.iter().any(breaks),
+        StmtType::If {
+            then_stmt,
+            else_stmt,
+            ..
+        } => breaks(then_stmt) || else_stmt.as_deref().is_some_and(breaks),
+        _ => false,
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use miette::{Diagnostic, NamedSource, SourceSpan};
+
+use crate::{ast::name::Name, interpreter::types::Type};
+
+#[derive(thiserror::Error, Debug, Diagnostic)]
+pub enum TypeError {
+    #[error("Expected {expected} but got {actual}")]
+    Mismatch {
+        expected: Type,
+        actual: Type,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("{actual}")]
+        location: SourceSpan,
+        #[label("declared as {expected}")]
+        annotation: SourceSpan,
+    },
+    #[error(
+        "Wrong operand type for operator \"{operator}\": expected {expected} but got {actual}"
+    )]
+    #[diagnostic(help("Change operand to {expected}"))]
+    WrongType {
+        operator: String,
+        expected: Type,
+        actual: Type,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("operator")]
+        operator_location: SourceSpan,
+        #[label("{actual}")]
+        operand_location: SourceSpan,
+    },
+    #[error("Wrong operand types for operator \"+\": expected both String or both Number but got {actual_lhs} and {actual_rhs}")]
+    #[diagnostic(help("Change operands to be both String or Number"))]
+    PlusOperatorWrongTypes {
+        actual_lhs: Type,
+        actual_rhs: Type,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("operator")]
+        operator_location: SourceSpan,
+        #[label("{actual_lhs}")]
+        lhs: SourceSpan,
+        #[label("{actual_rhs}")]
+        rhs: SourceSpan,
+    },
+    #[error("Variable {name} is declared as {expected} but nil without an initializer")]
+    #[diagnostic(help("Add an initializer or declare the variable as Nil"))]
+    Uninitialized {
+        name: Name,
+        expected: Type,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("declared as {expected}")]
+        annotation: SourceSpan,
+    },
+    #[error("Function {name} can end without returning {expected}")]
+    #[diagnostic(help("Add a return at the end of the function or declare it to return Nil"))]
+    MissingReturn {
+        name: Name,
+        expected: Type,
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("returns nil at the end")]
+        location: SourceSpan,
+        #[label("declared as {expected}")]
+        annotation: SourceSpan,
+    },
+}
+
+#[derive(thiserror::Error, Debug, Diagnostic)]
+#[error("Errors while type checking")]
+pub struct TypeErrors {
+    #[related]
+    pub type_errors: Vec<TypeError>,
+}
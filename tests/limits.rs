@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use miette::NamedSource;
+use rust_lox::{Limits, Lox, RuntimeError, Value};
+
+fn run_limited(limits: Limits, source: &str) -> miette::Result<()> {
+    let mut lox = Lox::builder().limits(limits).build();
+    let named_source = NamedSource::new("limits", source.to_string());
+    lox.run(source.to_string(), named_source)
+}
+
+fn runtime_error(result: miette::Result<()>) -> RuntimeError {
+    let err = result.expect_err("limit should have been exceeded");
+    match err.downcast::<RuntimeError>() {
+        Ok(err) => err,
+        Err(err) => panic!("expected runtime error but got {err:?}"),
+    }
+}
+
+#[test]
+fn statement_limit_stops_infinite_loop() {
+    let limits = Limits {
+        max_statements: Some(100),
+        ..Limits::default()
+    };
+    let source = "var i = 0;\nwhile (true) { i = i + 1; }";
+    let err = runtime_error(run_limited(limits, source));
+    assert!(
+        matches!(err, RuntimeError::StepLimitExceeded { limit: 100, location, .. } if location == (24, 14).into()),
+        "unexpected error {err:?}"
+    );
+}
+
+#[test]
+fn statement_limit_allows_short_programs() {
+    let limits = Limits {
+        max_statements: Some(100),
+        ..Limits::default()
+    };
+    run_limited(limits, "for (var i = 0; i < 10; i = i + 1) {}").unwrap();
+}
+
+#[test]
+fn statement_limit_is_per_run() {
+    let mut lox = Lox::builder()
+        .limits(Limits {
+            max_statements: Some(5),
+            ..Limits::default()
+        })
+        .build();
+    for run in 0..3 {
+        let source = "var a = 1; a = a + 1; print a;".to_string();
+        let named_source = NamedSource::new(format!("run {run}"), source.clone());
+        lox.run(source, named_source).unwrap();
+    }
+}
+
+#[test]
+fn call_depth_limit_stops_unbounded_recursion() {
+    let limits = Limits {
+        max_call_depth: Some(50),
+        ..Limits::default()
+    };
+    let source = "fun f(n) { return f(n + 1); }\nf(0);";
+    let err = runtime_error(run_limited(limits, source));
+    assert!(
+        matches!(err.error(), RuntimeError::StackOverflow { limit: 50, location, .. } if *location == (18, 8).into()),
+        "unexpected error {err:?}"
+    );
+}
+
+#[test]
+fn recursion_is_limited_by_default() {
+    let source = "fun f(n) { return f(n + 1); }\nf(0);";
+    let err = runtime_error(run_limited(Limits::default(), source));
+    assert!(
+        matches!(err.error(), RuntimeError::StackOverflow { limit, .. } if *limit == Limits::DEFAULT_MAX_CALL_DEPTH),
+        "unexpected error {err:?}"
+    );
+    let trace = err.trace();
+    assert_eq!(trace.len(), 2);
+    assert_eq!(trace[0].times, Limits::DEFAULT_MAX_CALL_DEPTH - 1);
+    assert_eq!(trace[1].location, (30, 4).into());
+}
+
+#[test]
+fn call_depth_limit_counts_native_calls() {
+    let limits = Limits {
+        max_call_depth: Some(1),
+        ..Limits::default()
+    };
+    let source = "fun f() { return len(\"a\"); }\nf();";
+    let err = runtime_error(run_limited(limits, source));
+    assert!(
+        matches!(err.error(), RuntimeError::StackOverflow { limit: 1, location, .. } if *location == (17, 8).into()),
+        "unexpected error {err:?}"
+    );
+}
+
+#[test]
+fn call_depth_limit_allows_bounded_recursion() {
+    let limits = Limits {
+        max_call_depth: Some(50),
+        ..Limits::default()
+    };
+    let source = "fun fib(n) { if (n < 2) return n; return fib(n - 1) + fib(n - 2); } fib(15);";
+    run_limited(limits, source).unwrap();
+}
+
+#[test]
+fn timeout_stops_infinite_loop() {
+    let limits = Limits {
+        timeout: Some(Duration::from_millis(50)),
+        ..Limits::default()
+    };
+    let err = runtime_error(run_limited(limits, "while (true) {}"));
+    assert!(
+        matches!(err, RuntimeError::Timeout { timeout, .. } if timeout == Duration::from_millis(50)),
+        "unexpected error {err:?}"
+    );
+}
+
+#[test]
+fn timeout_is_checked_on_calls_within_a_statement() {
+    let mut lox = Lox::builder()
+        .limits(Limits {
+            timeout: Some(Duration::from_millis(50)),
+            ..Limits::default()
+        })
+        .build();
+    lox.register_native("slow", 0, |_, _| {
+        std::thread::sleep(Duration::from_millis(20));
+        Ok(Value::Nil)
+    });
+    let source = "[slow(), slow(), slow(), slow(), slow()];";
+    let named_source = NamedSource::new("limits", source.to_string());
+    let err = runtime_error(lox.run(source.to_string(), named_source));
+    assert!(
+        matches!(err, RuntimeError::Timeout { location, .. } if location.offset() > 0),
+        "unexpected error {err:?}"
+    );
+}
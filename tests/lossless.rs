@@ -0,0 +1,59 @@
+use std::{fs, path::Path};
+
+use miette::NamedSource;
+use rust_lox::{Formatter, Parser, Scanner};
+
+/// The program of a datadriven test file, the text between the directive and the expected output.
+fn program(test_file: &str) -> &str {
+    let (_, rest) = test_file.split_once('\n').unwrap_or_default();
+    rest.split("\n----").next().unwrap_or_default()
+}
+
+fn lox_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            lox_files(&path, files);
+        } else if path.extension().is_some_and(|extension| extension == "lox") {
+            files.push(path);
+        }
+    }
+}
+
+#[test]
+fn every_test_program_round_trips() {
+    let mut files = vec![];
+    lox_files(Path::new("tests/"), &mut files);
+    let mut round_tripped = 0;
+    for file in files {
+        let contents = fs::read_to_string(&file).unwrap();
+        let source = program(&contents).to_string();
+        let named_source = NamedSource::new(file.display().to_string(), source.clone());
+        let Ok(tokens) = Scanner::scan_with_trivia(source.clone(), named_source) else {
+            continue;
+        };
+        let Ok(tree) = Parser::parse_lossless(tokens) else {
+            continue;
+        };
+        assert_eq!(tree.to_string(), source, "{}", file.display());
+        round_tripped += 1;
+    }
+    assert!(round_tripped > 30, "only {round_tripped} programs parsed");
+}
+
+#[test]
+fn formatting_every_test_program_is_idempotent() {
+    let mut files = vec![];
+    lox_files(Path::new("tests/"), &mut files);
+    for file in files {
+        let contents = fs::read_to_string(&file).unwrap();
+        let source = program(&contents).to_string();
+        let format = |source: &String| {
+            Formatter::format(source.clone(), NamedSource::new("", source.clone()))
+        };
+        let Ok(formatted) = format(&source) else {
+            continue;
+        };
+        assert_eq!(format(&formatted).unwrap(), formatted, "{}", file.display());
+    }
+}
@@ -0,0 +1,199 @@
+use std::{cell::RefCell, io::Write, rc::Rc};
+
+use miette::NamedSource;
+use rust_lox::{
+    ast::{expr::ExprType, name::Slot, stmt::StmtType},
+    Interpreter, Lint, LintWarning, Linter, Lox, Parser, ParserErrors, ResolutionError,
+    ResolutionErrors, Resolver, RuntimeError, Scanner, ScannerErrors, TypeErrors, Value,
+    VecPrinter, WriterPrinter,
+};
+
+fn run(lox: &mut Lox, source: &str) -> miette::Result<()> {
+    let named_source = NamedSource::new("public_api", source.to_string());
+    lox.run(source.to_string(), named_source)
+}
+
+/// Collects the arguments of every `emit(value)` call made by the program.
+fn with_emit(lox: &mut Lox) -> Rc<RefCell<Vec<Value>>> {
+    let emitted = Rc::new(RefCell::new(vec![]));
+    let sink = emitted.clone();
+    lox.register_native("emit", 1, move |_, mut arguments| {
+        sink.borrow_mut().push(arguments.remove(0));
+        Ok(Value::Nil)
+    });
+    emitted
+}
+
+#[test]
+fn run_program() {
+    let mut lox = Lox::new(false, false);
+    let emitted = with_emit(&mut lox);
+    run(
+        &mut lox,
+        "fun fib(n) { if (n < 2) return n; return fib(n - 1) + fib(n - 2); }
+         emit(fib(10));
+         emit(\"done\");",
+    )
+    .unwrap();
+    assert_eq!(
+        *emitted.borrow(),
+        vec![Value::Number(55.0), Value::String("done".to_string())]
+    );
+}
+
+#[test]
+fn collect_output_per_run() {
+    let printer = VecPrinter::new();
+    let mut lox = Lox::builder().printer(Box::new(printer.clone())).build();
+    run(&mut lox, "print 1; print \"two\";").unwrap();
+    assert_eq!(
+        printer.take_lines(),
+        vec![Value::Number(1.0), Value::String("two".to_string())]
+    );
+    run(&mut lox, "print nil;").unwrap();
+    assert_eq!(printer.get_output(), "Nil\n");
+}
+
+#[test]
+fn stream_output_to_writer() {
+    let shared = SharedBuffer::default();
+    let mut lox = Lox::builder()
+        .printer(Box::new(WriterPrinter::new(shared.clone())))
+        .build();
+    run(&mut lox, "for (var i = 0; i < 3; i = i + 1) print i;").unwrap();
+    assert_eq!(String::from_utf8(shared.0.take()).unwrap(), "0\n1\n2\n");
+}
+
+#[test]
+fn writer_printer_into_inner() {
+    let printer = WriterPrinter::new(vec![]);
+    rust_lox::Printer::print(&printer, Value::Boolean(true));
+    assert_eq!(printer.into_inner(), b"true\n");
+}
+
+/// `Lox` owns its printer, so the test keeps a handle on the same buffer.
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn state_is_kept_between_runs() {
+    let mut lox = Lox::new(false, false);
+    let emitted = with_emit(&mut lox);
+    run(&mut lox, "var counter = 1;").unwrap();
+    run(&mut lox, "counter = counter + 1; emit(counter);").unwrap();
+    assert_eq!(*emitted.borrow(), vec![Value::Number(2.0)]);
+}
+
+#[test]
+fn repl_evaluates_expressions() {
+    let mut lox = Lox::new(false, false);
+    assert_eq!(lox.run_repl("var a = 20;".to_string(), 1).unwrap(), None);
+    assert_eq!(
+        lox.run_repl("a + 22".to_string(), 2).unwrap(),
+        Some(Value::Number(42.0))
+    );
+}
+
+#[test]
+fn pipeline_stages() {
+    let source = "var a = 1; { var b = a; }".to_string();
+    let named_source = NamedSource::new("stages", source.clone());
+    let tokens = Scanner::scan(source, named_source, false).unwrap();
+    let mut statements = Parser::parse(tokens, false).unwrap();
+    assert_eq!(statements.len(), 2);
+    assert!(matches!(statements[0].stmt_type, StmtType::Var { .. }));
+    assert!(matches!(statements[1].stmt_type, StmtType::Block(_)));
+
+    Resolver::resolve(&mut statements, false).unwrap();
+    let StmtType::Block(block) = &statements[1].stmt_type else {
+        unreachable!()
+    };
+    let StmtType::Var {
+        name,
+        initializer: Some(initializer),
+        ..
+    } = &block[0].stmt_type
+    else {
+        panic!("expected a variable declaration")
+    };
+    assert_eq!(name.slot, Some(Slot::new(0, 0)));
+    let ExprType::Variable(a) = &initializer.expr_type else {
+        panic!("expected a variable")
+    };
+    assert_eq!(a.slot, None, "globals are not resolved to slots");
+
+    let mut interpreter = Interpreter::new();
+    interpreter.interpret(&statements).unwrap();
+}
+
+#[test]
+fn errors_of_every_stage() {
+    let mut lox = Lox::new(false, false);
+    let err = run(&mut lox, "var a = @;").unwrap_err();
+    assert!(err.downcast_ref::<ScannerErrors>().is_some());
+
+    let err = run(&mut lox, "print 1").unwrap_err();
+    assert!(err.downcast_ref::<ParserErrors>().is_some());
+
+    let err = run(&mut lox, "return 1;").unwrap_err();
+    let errors = &err
+        .downcast_ref::<ResolutionErrors>()
+        .unwrap()
+        .resolution_errors;
+    assert!(matches!(
+        errors[..],
+        [ResolutionError::InvalidReturn { .. }]
+    ));
+
+    let err = run(&mut lox, "var a: Number = nil;").unwrap_err();
+    assert!(err.downcast_ref::<TypeErrors>().is_some());
+
+    let err = run(&mut lox, "print 1 + nil;").unwrap_err();
+    assert!(err.downcast_ref::<RuntimeError>().is_some());
+}
+
+#[test]
+fn lint_without_running() {
+    let source = "fun f(a) { var b; }".to_string();
+    let named_source = NamedSource::new("lint", source.clone());
+    let tokens = Scanner::scan(source, named_source, false).unwrap();
+    let statements = Parser::parse(tokens, false).unwrap();
+    let lints = Linter::lint(&statements, [])
+        .iter()
+        .map(LintWarning::lint)
+        .collect::<Vec<_>>();
+    assert_eq!(lints, vec![Lint::UnusedParameter, Lint::UnusedVariable]);
+    assert_eq!("unused-parameter".parse(), Ok(Lint::UnusedParameter));
+}
+
+#[test]
+fn strict_repl_knows_earlier_globals() {
+    let mut lox = Lox::builder().strict(true).build();
+    lox.run_repl("var a = 20;".to_string(), 1).unwrap();
+    lox.run_repl("fun f() { return a + 1; }".to_string(), 2)
+        .unwrap();
+    assert_eq!(
+        lox.run_repl("f() + len(\"x\")".to_string(), 3).unwrap(),
+        Some(Value::Number(22.0))
+    );
+    let err = lox.run_repl("print b;".to_string(), 4).unwrap_err();
+    let errors = &err
+        .downcast_ref::<ResolutionErrors>()
+        .unwrap()
+        .resolution_errors;
+    assert!(matches!(
+        errors[..],
+        [ResolutionError::UndefinedVariable { .. }]
+    ));
+    assert!(lox.run_repl("b".to_string(), 5).is_err());
+}